@@ -0,0 +1,70 @@
+use proptest::arbitrary::{any, Arbitrary};
+use proptest::collection::vec;
+use proptest::prop_oneof;
+use proptest::strategy::{BoxedStrategy, Strategy};
+
+use crate::Deck;
+
+fn pile<T: Arbitrary + 'static>() -> BoxedStrategy<Vec<T>> {
+    prop_oneof![
+        1 => vec(any::<T>(), 0..=1),
+        3 => vec(any::<T>(), 0..64),
+    ]
+    .boxed()
+}
+
+impl<T: Arbitrary + 'static> Arbitrary for Deck<T> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (pile::<T>(), pile::<T>(), pile::<T>())
+            .prop_map(|(draw_pile, discard_pile, removed_pile)| Deck {
+                draw_pile,
+                discard_pile,
+                removed_pile,
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::strategy::ValueTree;
+    use proptest::test_runner::TestRunner;
+    use proptest::{prop_assert_eq, proptest};
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_draw_put_top(mut deck in any::<Deck<u8>>()) {
+            let before = deck.peek_draw_pile().to_vec();
+            if let Some(x) = deck.draw_top() {
+                deck.put_top(x);
+            }
+            prop_assert_eq!(deck.peek_draw_pile(), &before);
+        }
+    }
+
+    #[test]
+    fn test_pile_sizes() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = any::<Deck<u8>>();
+        let sizes: Vec<_> = (0..256)
+            .map(|_| {
+                strategy
+                    .new_tree(&mut runner)
+                    .unwrap()
+                    .current()
+                    .pile_sizes()
+            })
+            .collect();
+
+        for size in [0, 1] {
+            assert!(sizes.iter().any(|s| s.0 == size));
+            assert!(sizes.iter().any(|s| s.1 == size));
+            assert!(sizes.iter().any(|s| s.2 == size));
+        }
+    }
+}
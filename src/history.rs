@@ -0,0 +1,141 @@
+use std::collections::VecDeque;
+
+use crate::Deck;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryEvent<T> {
+    Drew(T),
+    Discarded(T),
+    Removed(T),
+}
+
+pub struct DeckWithHistory<T> {
+    inner: Deck<T>,
+    history: VecDeque<HistoryEvent<T>>,
+    max_history: usize,
+}
+
+impl<T: Clone> DeckWithHistory<T> {
+    pub fn new(inner: Deck<T>, max_history: usize) -> Self {
+        Self {
+            inner,
+            history: VecDeque::with_capacity(max_history),
+            max_history,
+        }
+    }
+
+    pub fn draw_top(&mut self) -> Option<T> {
+        let x = self.inner.draw_top()?;
+        self.record(HistoryEvent::Drew(x.clone()));
+        Some(x)
+    }
+
+    pub fn discard(&mut self, x: T) {
+        self.record(HistoryEvent::Discarded(x.clone()));
+        self.inner.discard(x);
+    }
+
+    pub fn remove(&mut self, x: T) {
+        self.record(HistoryEvent::Removed(x.clone()));
+        self.inner.remove(x);
+    }
+
+    pub fn get_history(&self) -> &VecDeque<HistoryEvent<T>> {
+        &self.history
+    }
+
+    pub fn undo_last(&mut self) -> bool {
+        match self.history.pop_back() {
+            Some(HistoryEvent::Drew(x)) => self.inner.put_top(x),
+            Some(HistoryEvent::Discarded(_)) => {
+                self.inner.pop_discard();
+            }
+            Some(HistoryEvent::Removed(_)) => {
+                self.inner.pop_removed();
+            }
+            None => return false,
+        }
+
+        true
+    }
+
+    pub fn deck(&self) -> &Deck<T> {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> Deck<T> {
+        self.inner
+    }
+
+    fn record(&mut self, event: HistoryEvent<T>) {
+        if self.max_history == 0 {
+            return;
+        }
+
+        if self.history.len() == self.max_history {
+            self.history.pop_front();
+        }
+        self.history.push_back(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history() {
+        let mut deck = DeckWithHistory::new(Deck::from(vec![1_u8, 2, 3]), 3);
+        assert!(deck.get_history().is_empty());
+
+        let x = deck.draw_top().unwrap();
+        deck.discard(x);
+        let y = deck.draw_top().unwrap();
+        deck.remove(y);
+
+        assert_eq!(
+            deck.get_history(),
+            &[
+                HistoryEvent::Discarded(3),
+                HistoryEvent::Drew(2),
+                HistoryEvent::Removed(2),
+            ]
+        );
+        assert_eq!(deck.deck().peek_draw_pile(), [1]);
+        assert_eq!(deck.deck().peek_discard_pile(), [3]);
+        assert_eq!(deck.deck().peek_removed_pile(), [2]);
+    }
+
+    #[test]
+    fn test_undo_last() {
+        let mut deck = DeckWithHistory::new(Deck::from(vec![1_u8, 2, 3]), 10);
+        assert!(!deck.undo_last());
+
+        let x = deck.draw_top().unwrap();
+        deck.discard(x);
+        let y = deck.draw_top().unwrap();
+        deck.remove(y);
+
+        assert!(deck.undo_last());
+        assert!(deck.deck().peek_removed_pile().is_empty());
+        assert_eq!(deck.deck().peek_draw_pile(), [1]);
+
+        assert!(deck.undo_last());
+        assert_eq!(deck.deck().peek_draw_pile(), [1, 2]);
+
+        assert!(deck.undo_last());
+        assert!(deck.deck().peek_discard_pile().is_empty());
+
+        assert!(deck.undo_last());
+        assert!(!deck.undo_last());
+        assert_eq!(deck.into_inner(), Deck::from(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_no_history() {
+        let mut deck = DeckWithHistory::new(Deck::from(vec![1_u8]), 0);
+        deck.draw_top();
+        assert!(deck.get_history().is_empty());
+        assert!(!deck.undo_last());
+    }
+}
@@ -1,7 +1,31 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display, Formatter};
+use std::hash::Hash;
+use std::iter::Sum;
+use std::ops::{Index, IndexMut};
+
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::index::sample;
 use rand::seq::SliceRandom;
-use rand::{thread_rng, Rng};
+use rand::{thread_rng, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+#[cfg(feature = "proptest")]
+mod arbitrary;
+mod history;
+mod multi_pile;
+mod shared;
+mod transaction;
 
+pub use history::{DeckWithHistory, HistoryEvent};
+pub use multi_pile::MultiPileDeck;
+pub use shared::SharedDeck;
+pub use transaction::DeckTransaction;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Deck<T> {
+    // Hash covers the piles in field order: draw, discard, removed. Keep this order stable.
     draw_pile: Vec<T>,
     discard_pile: Vec<T>,
     removed_pile: Vec<T>,
@@ -16,8 +40,29 @@ impl<T> Deck<T> {
         }
     }
 
+    pub fn new_with_draw(cards: Vec<T>) -> Self {
+        Self::from(cards)
+    }
+
+    pub fn from_seed_and_items(seed: u64, items: Vec<T>) -> Self {
+        let mut deck = Self::from(items);
+        deck.shuffle_draw_seeded(seed);
+        deck
+    }
+
+    pub fn merge(decks: impl IntoIterator<Item = Deck<T>>) -> Deck<T> {
+        let mut merged = Self::new();
+        for deck in decks {
+            merged.draw_pile.extend(deck.draw_pile);
+            merged.discard_pile.extend(deck.discard_pile);
+            merged.removed_pile.extend(deck.removed_pile);
+        }
+
+        merged
+    }
+
     pub fn can_draw(&self) -> bool {
-        self.draw_pile.len() > 0
+        !self.draw_pile.is_empty()
     }
 
     pub fn draw_top(&mut self) -> Option<T> {
@@ -32,6 +77,72 @@ impl<T> Deck<T> {
         Some(self.draw_pile.remove(0))
     }
 
+    pub fn draw_top_or_else<F: FnOnce() -> T>(&mut self, default: F) -> T {
+        self.draw_pile.pop().unwrap_or_else(default)
+    }
+
+    pub fn draw_at(&mut self, index: usize) -> Option<T> {
+        if index >= self.draw_pile.len() {
+            return None;
+        }
+
+        Some(self.draw_pile.remove(index))
+    }
+
+    pub fn weighted_draw(&mut self, weights: &[f64]) -> Option<T> {
+        if self.draw_pile.is_empty() {
+            return None;
+        }
+
+        assert_eq!(
+            weights.len(),
+            self.draw_pile.len(),
+            "weights must match the draw pile length"
+        );
+        assert!(
+            weights.iter().all(|w| w.is_finite()),
+            "weights must be finite"
+        );
+        assert!(
+            weights.iter().all(|w| *w >= 0.0),
+            "weights must not be negative"
+        );
+        assert!(
+            weights.iter().any(|w| *w > 0.0),
+            "at least one weight must be positive"
+        );
+
+        let distribution = WeightedIndex::new(weights).expect("invalid weights");
+        let index = distribution.sample(&mut thread_rng());
+        Some(self.draw_pile.remove(index))
+    }
+
+    pub fn draw_random_n(&mut self, n: usize) -> Vec<T> {
+        let n = n.min(self.draw_pile.len());
+        let mut indices = sample(&mut thread_rng(), self.draw_pile.len(), n).into_vec();
+        indices.sort_unstable_by(|a, b| b.cmp(a));
+
+        indices
+            .into_iter()
+            .filter_map(|i| self.draw_at(i))
+            .collect()
+    }
+
+    pub fn drain_draw_top_n(&mut self, n: usize) -> impl Iterator<Item = T> + '_ {
+        let start = self.draw_pile.len() - n.min(self.draw_pile.len());
+        self.draw_pile.drain(start..).rev()
+    }
+
+    pub fn filter_map_draw<U, F: FnMut(T) -> Option<U>>(&mut self, f: F) -> Vec<U> {
+        self.draw_pile.drain(..).filter_map(f).collect()
+    }
+
+    pub fn drain_discard_sorted_by<F: FnMut(&T, &T) -> Ordering>(&mut self, compare: F) -> Vec<T> {
+        let mut drained = std::mem::take(&mut self.discard_pile);
+        drained.sort_by(compare);
+        drained
+    }
+
     pub fn put_top(&mut self, x: T) {
         self.draw_pile.push(x);
     }
@@ -40,6 +151,10 @@ impl<T> Deck<T> {
         self.draw_pile.insert(0, x);
     }
 
+    pub fn insert_at_position_from_bottom(&mut self, item: T, n: usize) {
+        self.draw_pile.insert(n.min(self.draw_pile.len()), item);
+    }
+
     pub fn put_sparse(&mut self, elements: Vec<T>) {
         if elements.is_empty() {
             return;
@@ -65,206 +180,3271 @@ impl<T> Deck<T> {
         }
     }
 
-    pub fn discard(&mut self, x: T) {
-        self.discard_pile.push(x);
+    pub fn put_sparse_even(&mut self, elements: Vec<T>) {
+        if elements.is_empty() {
+            return;
+        }
+
+        let n = elements.len();
+        let len = self.draw_pile.len() + n;
+        let mut pile = std::mem::replace(&mut self.draw_pile, Vec::with_capacity(len)).into_iter();
+        let mut elements = elements.into_iter().enumerate().peekable();
+
+        for position in 0..len {
+            match elements.next_if(|(i, _)| i * len / n == position) {
+                Some((_, x)) => self.draw_pile.push(x),
+                None => self.draw_pile.extend(pile.next()),
+            }
+        }
     }
 
-    pub fn remove(&mut self, x: T) {
-        self.removed_pile.push(x);
+    pub fn put_sparse_batched(&mut self, batches: Vec<Vec<T>>) {
+        for batch in batches {
+            self.put_sparse(batch);
+        }
     }
 
-    pub fn remaining(&self) -> usize {
-        self.draw_pile.len()
+    pub fn put_sparse_exact(&mut self, elements: Vec<T>, positions: Vec<usize>) {
+        assert_eq!(
+            elements.len(),
+            positions.len(),
+            "put_sparse_exact needs exactly one position per element"
+        );
+        assert!(
+            positions.windows(2).all(|w| w[0] < w[1]),
+            "put_sparse_exact positions must be strictly increasing"
+        );
+        for (i, position) in positions.iter().enumerate() {
+            assert!(
+                *position <= self.draw_pile.len() + i,
+                "put_sparse_exact position {} is out of range for insertion {}",
+                position,
+                i
+            );
+        }
+
+        let len = self.draw_pile.len() + elements.len();
+        let mut pile = std::mem::replace(&mut self.draw_pile, Vec::with_capacity(len)).into_iter();
+        let mut elements = elements.into_iter().zip(positions).peekable();
+
+        for position in 0..len {
+            match elements.next_if(|(_, p)| *p == position) {
+                Some((x, _)) => self.draw_pile.push(x),
+                None => self.draw_pile.extend(pile.next()),
+            }
+        }
     }
 
-    pub fn see_draw(&self) -> &[T] {
-        self.draw_pile.as_slice()
+    pub fn deal_round_robin(&mut self, n_players: usize) -> Vec<Vec<T>> {
+        let mut hands: Vec<Vec<T>> = (0..n_players).map(|_| Vec::new()).collect();
+        if n_players == 0 {
+            return hands;
+        }
+
+        let mut player = 0_usize;
+        while let Some(x) = self.draw_top() {
+            hands[player].push(x);
+            player = (player + 1) % n_players;
+        }
+
+        hands
     }
 
-    pub fn see_discarded(&self) -> &[T] {
-        self.discard_pile.as_slice()
+    pub fn deal_equal(&mut self, n_hands: usize, cards_each: usize) -> Option<Vec<Vec<T>>> {
+        if n_hands.checked_mul(cards_each)? > self.draw_pile.len() {
+            return None;
+        }
+
+        let mut hands: Vec<Vec<T>> = (0..n_hands)
+            .map(|_| Vec::with_capacity(cards_each))
+            .collect();
+        for _ in 0..cards_each {
+            for hand in hands.iter_mut() {
+                hand.push(self.draw_top()?);
+            }
+        }
+
+        Some(hands)
     }
 
-    pub fn see_removed(&self) -> &[T] {
-        self.removed_pile.as_slice()
+    pub fn sieve_draw(&mut self, pattern: &[bool]) {
+        if pattern.is_empty() {
+            return;
+        }
+
+        let pile = std::mem::take(&mut self.draw_pile);
+        for (x, keep) in pile.into_iter().zip(pattern.iter().cycle()) {
+            if *keep {
+                self.draw_pile.push(x);
+            } else {
+                self.discard_pile.push(x);
+            }
+        }
     }
 
-    pub fn shuffle_draw(&mut self) {
-        self.draw_pile.as_mut_slice().shuffle(&mut thread_rng());
+    pub fn filter_draw_into_discard<F: FnMut(&T) -> bool>(&mut self, mut predicate: F) -> usize {
+        let before = self.discard_pile.len();
+        let pile = std::mem::take(&mut self.draw_pile);
+        for x in pile {
+            if predicate(&x) {
+                self.draw_pile.push(x);
+            } else {
+                self.discard_pile.push(x);
+            }
+        }
+
+        self.discard_pile.len() - before
     }
 
-    pub fn shuffle_discard(&mut self) {
-        self.discard_pile.as_mut_slice().shuffle(&mut thread_rng());
+    pub fn draw_matching_and_discard_others(
+        &mut self,
+        n: usize,
+        mut predicate: impl FnMut(&T) -> bool,
+    ) -> Vec<T> {
+        let mut found = Vec::new();
+        while found.len() < n {
+            let Some(x) = self.draw_pile.pop() else {
+                break;
+            };
+
+            if predicate(&x) {
+                found.push(x);
+            } else {
+                self.discard_pile.push(x);
+            }
+        }
+
+        found
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    pub fn peek_top_and_discard_if<F: FnMut(&T) -> bool>(&mut self, mut predicate: F) -> bool {
+        if !self.draw_pile.last().is_some_and(&mut predicate) {
+            return false;
+        }
 
-    #[test]
-    fn test_draw() {
-        let mut deck = Deck::<u8>::new();
+        let x = self.draw_pile.pop().unwrap();
+        self.discard_pile.push(x);
+        true
+    }
 
-        assert!(!deck.can_draw());
-        assert_eq!(deck.draw_pile.len(), 0);
-        assert_eq!(deck.remaining(), 0);
-        assert_eq!(deck.draw_top(), None);
-        assert_eq!(deck.draw_bottom(), None);
+    pub fn draw_until_n_found<F: FnMut(&T) -> bool>(
+        &mut self,
+        mut predicate: F,
+        n: usize,
+    ) -> (Vec<T>, Vec<T>) {
+        let mut matching = Vec::new();
+        let mut others = Vec::new();
+        while matching.len() < n {
+            let Some(x) = self.draw_pile.pop() else {
+                break;
+            };
 
-        deck.put_top(11);
-        assert_eq!(deck.draw_pile.len(), 1);
-        assert_eq!(deck.remaining(), 1);
-        assert!(deck.can_draw());
+            if predicate(&x) {
+                matching.push(x);
+            } else {
+                others.push(x);
+            }
+        }
 
-        deck.put_top(7);
-        assert_eq!(deck.draw_pile.len(), 2);
-        assert_eq!(deck.remaining(), 2);
-        assert!(deck.can_draw());
+        (matching, others)
+    }
 
-        deck.put_bottom(5);
-        assert_eq!(deck.draw_pile.len(), 3);
-        assert_eq!(deck.remaining(), 3);
-        assert!(deck.can_draw());
+    pub fn bulk_move_to_discard(&mut self, indices: &[usize]) {
+        assert!(
+            indices.windows(2).all(|w| w[0] < w[1]),
+            "indices must be sorted in strictly ascending order"
+        );
+        if let Some(last) = indices.last() {
+            assert!(
+                *last < self.draw_pile.len(),
+                "index {} is out of bounds for a draw pile of {} cards",
+                last,
+                self.draw_pile.len()
+            );
+        }
 
-        assert_eq!(deck.draw_top(), Some(7));
-        assert!(deck.can_draw());
-        assert_eq!(deck.draw_bottom(), Some(5));
-        assert!(deck.can_draw());
-        assert_eq!(deck.draw_bottom(), Some(11));
+        let mut moved: Vec<T> = indices
+            .iter()
+            .rev()
+            .map(|i| self.draw_pile.remove(*i))
+            .collect();
+        moved.reverse();
+        self.discard_pile.extend(moved);
+    }
 
-        assert!(!deck.can_draw());
-        assert_eq!(deck.draw_top(), None);
-        assert_eq!(deck.draw_bottom(), None);
+    pub fn transfer_top_n_to(&mut self, other: &mut Deck<T>, n: usize) -> usize {
+        let n = n.min(self.draw_pile.len());
+        let packet = self.draw_pile.split_off(self.draw_pile.len() - n);
+        other.draw_pile.extend(packet);
+
+        n
     }
 
-    #[test]
-    fn test_shuffle_draw() {
-        let mut deck = Deck::<u8>::new();
-        deck.put_top(1);
-        deck.put_bottom(2);
-        for _ in 0..2 {
-            deck.put_top(0);
-            deck.put_bottom(0);
+    pub fn drain_while_into_other<F: FnMut(&T) -> bool>(
+        &mut self,
+        other: &mut Deck<T>,
+        mut predicate: F,
+    ) -> usize {
+        let mut count = 0_usize;
+        while self.draw_pile.last().is_some_and(&mut predicate) {
+            other.put_top(self.draw_pile.pop().unwrap());
+            count += 1;
         }
 
-        for _ in 0..10000 {
-            // just try long enough
-            deck.shuffle_draw();
-            if deck.draw_pile.last() == Some(&1) {
-                break;
+        count
+    }
+
+    pub fn move_all_draw_to_other(&mut self, other: &mut Deck<T>) {
+        other.draw_pile.append(&mut self.draw_pile);
+    }
+
+    pub fn discard(&mut self, x: T) {
+        self.discard_pile.push(x);
+    }
+
+    pub fn put_discard_top(&mut self, item: T) {
+        self.discard(item);
+    }
+
+    pub fn put_discard_bottom(&mut self, item: T) {
+        self.discard_pile.insert(0, item);
+    }
+
+    pub fn move_draw_top_to_discard_bottom(&mut self) -> bool {
+        match self.draw_pile.pop() {
+            Some(x) => {
+                self.discard_pile.insert(0, x);
+                true
             }
+            None => false,
         }
-        assert_eq!(deck.draw_top(), Some(1));
+    }
 
-        for _ in 0..10000 {
-            // again
-            deck.shuffle_draw();
-            if deck.draw_pile.last() == Some(&2) {
-                break;
+    pub fn move_draw_bottom_to_discard_top(&mut self) -> bool {
+        match self.draw_bottom() {
+            Some(x) => {
+                self.discard_pile.push(x);
+                true
             }
+            None => false,
         }
-        assert_eq!(deck.draw_top(), Some(2));
     }
 
-    #[test]
-    fn test_shuffle_discard() {
-        let mut deck = Deck::<u8>::new();
-        deck.discard(0);
-        deck.discard(1);
-        assert_eq!(deck.see_discarded(), [0, 1]);
-
-        for _ in 0..1000 {
-            // just try long enough
-            deck.shuffle_discard();
-            if deck.discard_pile.last() == Some(&0) {
-                break;
+    pub fn move_draw_top_to_removed_bottom(&mut self) -> bool {
+        match self.draw_pile.pop() {
+            Some(x) => {
+                self.removed_pile.insert(0, x);
+                true
             }
+            None => false,
         }
+    }
 
-        assert_eq!(deck.see_discarded(), [1, 0]);
+    pub fn move_draw_bottom_to_removed_top(&mut self) -> bool {
+        match self.draw_bottom() {
+            Some(x) => {
+                self.removed_pile.push(x);
+                true
+            }
+            None => false,
+        }
     }
 
-    #[test]
-    fn test_remove() {
-        let mut deck = Deck::<u8>::new();
-        assert_eq!(deck.removed_pile.len(), 0);
-        deck.remove(3);
-        assert_eq!(deck.removed_pile.len(), 1);
-        deck.remove(8);
-        assert_eq!(deck.removed_pile.len(), 2);
-        assert_eq!(deck.see_removed(), [3, 8]);
+    pub fn mill(&mut self, n: usize) -> usize {
+        let n = n.min(self.draw_pile.len());
+        for _ in 0..n {
+            let x = self.draw_pile.pop().unwrap();
+            self.discard_pile.push(x);
+        }
+
+        n
     }
 
-    #[test]
-    fn test_discard() {
-        let mut deck = Deck::<u8>::new();
-        assert_eq!(deck.discard_pile.len(), 0);
-        deck.discard(5);
-        assert_eq!(deck.discard_pile.len(), 1);
-        deck.discard(7);
-        assert_eq!(deck.discard_pile.len(), 2);
-        assert_eq!(deck.see_discarded(), [5, 7]);
+    pub fn move_n_from_discard_to_draw(&mut self, n: usize) -> usize {
+        let n = n.min(self.discard_pile.len());
+        let packet = self.discard_pile.split_off(self.discard_pile.len() - n);
+        self.draw_pile.splice(0..0, packet);
+
+        n
     }
 
-    #[test]
-    fn test_put_sparse() {
-        sub_test_sparse_zero(50);
+    pub fn move_cards_from_discard_to_draw_by_predicate<F: FnMut(&T) -> bool>(
+        &mut self,
+        mut f: F,
+    ) -> usize {
+        let (matching, others): (Vec<T>, Vec<T>) = std::mem::take(&mut self.discard_pile)
+            .into_iter()
+            .partition(|x| f(x));
+        self.discard_pile = others;
 
-        for deck_size in 0..60 {
-            for n_insert in 1..(deck_size + 1) {
-                sub_test_sparse(deck_size, n_insert);
-            }
-        }
+        let n = matching.len();
+        self.draw_pile.splice(0..0, matching);
+        n
     }
 
-    fn sub_test_sparse_zero(initial_deck_size: usize) {
-        let mut deck = Deck::<usize>::new();
-        (0..initial_deck_size).for_each(|i| deck.put_top(i));
+    pub fn remove(&mut self, x: T) {
+        self.removed_pile.push(x);
+    }
 
-        deck.put_sparse(Vec::new());
-        assert_eq!(deck.draw_pile.len(), initial_deck_size);
-        for i in 0..deck.draw_pile.len() {
-            assert_eq!(deck.draw_pile[i], i);
-        }
+    pub fn pop_discard(&mut self) -> Option<T> {
+        self.discard_pile.pop()
     }
 
-    fn sub_test_sparse(initial_deck_size: usize, n_insert: usize) {
-        let mut deck = Deck::<usize>::new();
-        (0..initial_deck_size).for_each(|i| deck.put_top(i));
+    pub fn pop_removed(&mut self) -> Option<T> {
+        self.removed_pile.pop()
+    }
 
-        deck.put_sparse((initial_deck_size..initial_deck_size + n_insert).collect());
-        assert_eq!(deck.draw_pile.len(), initial_deck_size + n_insert);
+    pub fn extend_discard(&mut self, iter: impl IntoIterator<Item = T>) {
+        self.discard_pile.extend(iter);
+    }
 
-        let bucket_standard_size = initial_deck_size / n_insert + 1;
-        let mut carry = initial_deck_size % n_insert;
-        let mut start_counter: usize = 0;
-        let mut remaining = deck.draw_pile.as_slice();
-        let mut expected = initial_deck_size;
+    pub fn extend_removed(&mut self, iter: impl IntoIterator<Item = T>) {
+        self.removed_pile.extend(iter);
+    }
 
-        while !remaining.is_empty() {
-            let size = bucket_standard_size
-                + if carry > 0 {
-                    carry -= 1;
-                    1
-                } else {
-                    0
-                };
+    pub fn remaining(&self) -> usize {
+        self.draw_pile.len()
+    }
 
-            let (bucket, tail) = remaining.split_at(size);
+    pub fn discard_count(&self) -> usize {
+        self.discard_pile.len()
+    }
 
-            // check bucket
-            assert_eq!(bucket.len(), size);
-            assert!(bucket.contains(&expected));
+    pub fn removed_count(&self) -> usize {
+        self.removed_pile.len()
+    }
 
-            for (i, e) in bucket.iter().filter(|x| **x != expected).enumerate() {
-                assert_eq!(*e, start_counter + i);
-            }
+    pub fn total_count(&self) -> usize {
+        self.draw_pile.len() + self.discard_pile.len() + self.removed_pile.len()
+    }
 
-            // loop
-            remaining = tail;
-            start_counter += size - 1;
-            expected += 1;
+    pub fn pile_sizes(&self) -> (usize, usize, usize) {
+        (
+            self.draw_pile.len(),
+            self.discard_pile.len(),
+            self.removed_pile.len(),
+        )
+    }
+
+    pub fn remaining_fraction(&self) -> f64 {
+        let total = self.total_count();
+        if total == 0 {
+            return 0.0;
         }
+
+        self.draw_pile.len() as f64 / total as f64
+    }
+
+    pub fn hypergeometric_draw_probability(&self, successes_in_deck: usize, draws: usize) -> f64 {
+        let n = self.draw_pile.len();
+        assert!(
+            successes_in_deck <= n,
+            "{} successes cannot fit in a draw pile of {} cards",
+            successes_in_deck,
+            n
+        );
+
+        let draws = draws.min(n);
+        if successes_in_deck == 0 || draws == 0 {
+            return 0.0;
+        }
+        if draws > n - successes_in_deck {
+            return 1.0;
+        }
+
+        // ln(C(n - k, d) / C(n, d)) as a sum of logarithms to avoid overflow
+        let failures = n - successes_in_deck;
+        let ln_no_success: f64 = (0..draws)
+            .map(|i| ((failures - i) as f64).ln() - ((n - i) as f64).ln())
+            .sum();
+
+        1.0 - ln_no_success.exp()
+    }
+
+    pub fn stats(&self) -> DeckStats {
+        DeckStats {
+            draw_count: self.remaining(),
+            discard_count: self.discard_count(),
+            removed_count: self.removed_count(),
+            total: self.total_count(),
+            draw_fraction: self.remaining_fraction(),
+        }
+    }
+
+    pub fn peek_draw_pile(&self) -> &[T] {
+        self.draw_pile.as_slice()
+    }
+
+    pub fn peek_discard_pile(&self) -> &[T] {
+        self.discard_pile.as_slice()
+    }
+
+    pub fn peek_removed_pile(&self) -> &[T] {
+        self.removed_pile.as_slice()
+    }
+
+    pub fn peek_draw_pile_mut(&mut self) -> &mut [T] {
+        self.draw_pile.as_mut_slice()
+    }
+
+    pub fn peek_discard_pile_mut(&mut self) -> &mut [T] {
+        self.discard_pile.as_mut_slice()
+    }
+
+    pub fn peek_removed_pile_mut(&mut self) -> &mut [T] {
+        self.removed_pile.as_mut_slice()
+    }
+
+    #[deprecated(note = "use `peek_draw_pile` instead")]
+    pub fn see_draw(&self) -> &[T] {
+        self.peek_draw_pile()
+    }
+
+    #[deprecated(note = "use `peek_discard_pile` instead")]
+    pub fn see_discarded(&self) -> &[T] {
+        self.peek_discard_pile()
+    }
+
+    #[deprecated(note = "use `peek_removed_pile` instead")]
+    pub fn see_removed(&self) -> &[T] {
+        self.peek_removed_pile()
+    }
+
+    pub fn peek_all_piles(&self) -> (&[T], &[T], &[T]) {
+        (
+            self.draw_pile.as_slice(),
+            self.discard_pile.as_slice(),
+            self.removed_pile.as_slice(),
+        )
+    }
+
+    #[deprecated(note = "use `peek_all_piles` instead")]
+    pub fn see_all_piles(&self) -> (&[T], &[T], &[T]) {
+        self.peek_all_piles()
+    }
+
+    pub fn peek_top(&self) -> Option<&T> {
+        self.draw_pile.last()
+    }
+
+    pub fn draw_iter_top_first(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.draw_pile.iter().rev()
+    }
+
+    pub fn make_contiguous(&mut self) {
+        // the draw pile is a Vec, which is always contiguous
+    }
+
+    pub fn as_contiguous_draw_slice(&self) -> &[T] {
+        self.draw_pile.as_slice()
+    }
+
+    pub fn iter_draw_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.draw_pile.iter_mut()
+    }
+
+    pub fn iter_discard_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.discard_pile.iter_mut()
+    }
+
+    pub fn iter_removed_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.removed_pile.iter_mut()
+    }
+
+    pub fn zip_piles(&self) -> ZipPilesIter<'_, T> {
+        ZipPilesIter {
+            draw: self.draw_pile.iter(),
+            discard: self.discard_pile.iter(),
+            removed: self.removed_pile.iter(),
+        }
+    }
+
+    pub fn draw_pile_as_mut_slice(&mut self) -> &mut [T] {
+        self.draw_pile.as_mut_slice()
+    }
+
+    pub fn discard_pile_as_mut_slice(&mut self) -> &mut [T] {
+        self.discard_pile.as_mut_slice()
+    }
+
+    pub fn removed_pile_as_mut_slice(&mut self) -> &mut [T] {
+        self.removed_pile.as_mut_slice()
+    }
+
+    pub fn peek_at(&self, index: usize) -> Option<&T> {
+        self.draw_pile.get(index)
+    }
+
+    pub fn peek_at_discard(&self, index: usize) -> Option<&T> {
+        self.discard_pile.get(index)
+    }
+
+    pub fn peek_at_removed(&self, index: usize) -> Option<&T> {
+        self.removed_pile.get(index)
+    }
+
+    pub fn sample_draw_indices(&self, n: usize) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..self.draw_pile.len()).collect();
+        let n = n.min(indices.len());
+        let (sample, _) = indices.partial_shuffle(&mut thread_rng(), n);
+
+        let mut sample = sample.to_vec();
+        sample.sort_unstable();
+        sample
+    }
+
+    pub fn peek_top_n(&self, n: usize) -> &[T] {
+        &self.draw_pile[self.draw_pile.len() - n.min(self.draw_pile.len())..]
+    }
+
+    pub fn peek_discard_n(&self, n: usize) -> &[T] {
+        &self.discard_pile[self.discard_pile.len() - n.min(self.discard_pile.len())..]
+    }
+
+    pub fn peek_removed_n(&self, n: usize) -> &[T] {
+        &self.removed_pile[self.removed_pile.len() - n.min(self.removed_pile.len())..]
+    }
+
+    pub fn peek_bottom_n_from_draw(&self, n: usize) -> &[T] {
+        &self.draw_pile[..n.min(self.draw_pile.len())]
+    }
+
+    pub fn top_n_matching<F: FnMut(&T) -> bool>(&self, n: usize, mut predicate: F) -> Vec<&T> {
+        self.draw_pile
+            .iter()
+            .rev()
+            .filter(|x| predicate(x))
+            .take(n)
+            .collect()
+    }
+
+    pub fn shuffle_draw(&mut self) {
+        self.draw_pile.as_mut_slice().shuffle(&mut thread_rng());
+    }
+
+    pub fn shuffle_discard(&mut self) {
+        self.discard_pile.as_mut_slice().shuffle(&mut thread_rng());
+    }
+
+    // ChaCha8Rng gives the same stream on every platform, unlike SmallRng.
+    pub fn shuffle_draw_seeded(&mut self, seed: u64) {
+        self.draw_pile.shuffle(&mut ChaCha8Rng::seed_from_u64(seed));
+    }
+
+    pub fn shuffle_discard_seeded(&mut self, seed: u64) {
+        self.discard_pile
+            .shuffle(&mut ChaCha8Rng::seed_from_u64(seed));
+    }
+
+    pub fn shuffle_removed_seeded(&mut self, seed: u64) {
+        self.removed_pile
+            .shuffle(&mut ChaCha8Rng::seed_from_u64(seed));
+    }
+
+    pub fn shuffle_draw_n_times(&mut self, n: usize) {
+        self.shuffle_draw_n_times_with_rng(n, &mut thread_rng());
+    }
+
+    pub fn shuffle_draw_n_times_with_rng<R: Rng>(&mut self, n: usize, rng: &mut R) {
+        for _ in 0..n {
+            self.draw_pile.shuffle(rng);
+        }
+    }
+
+    pub fn riffle_shuffle(&mut self) {
+        self.riffle_shuffle_with_rng(&mut thread_rng());
+    }
+
+    pub fn riffle_shuffle_with_rng<R: Rng>(&mut self, rng: &mut R) {
+        let n = self.draw_pile.len();
+        let cut = (0..n).filter(|_| rng.gen_bool(0.5)).count();
+
+        let right = self.draw_pile.split_off(cut);
+        let left = std::mem::replace(&mut self.draw_pile, Vec::with_capacity(n));
+        let (mut left_remaining, mut right_remaining) = (left.len(), right.len());
+        let (mut left, mut right) = (left.into_iter(), right.into_iter());
+
+        while left_remaining + right_remaining > 0 {
+            if rng.gen_range(0..left_remaining + right_remaining) < left_remaining {
+                self.draw_pile.extend(left.next());
+                left_remaining -= 1;
+            } else {
+                self.draw_pile.extend(right.next());
+                right_remaining -= 1;
+            }
+        }
+    }
+
+    pub fn overhand_shuffle(&mut self) {
+        self.overhand_shuffle_with_rng(&mut thread_rng());
+    }
+
+    pub fn overhand_shuffle_with_rng<R: Rng>(&mut self, rng: &mut R) {
+        let n = self.draw_pile.len();
+        let mut source = std::mem::replace(&mut self.draw_pile, Vec::with_capacity(n));
+
+        while !source.is_empty() {
+            let mut size = 1;
+            while size < source.len() && !rng.gen_bool(0.25) {
+                size += 1;
+            }
+
+            let packet = source.split_off(source.len() - size);
+            self.draw_pile.extend(packet);
+        }
+    }
+
+    pub fn shuffle_between_indices(&mut self, start: usize, end: usize) {
+        self.shuffle_range_with_rng(start, end, &mut thread_rng());
+    }
+
+    pub fn shuffle_range_with_rng<R: Rng>(&mut self, start: usize, end: usize, rng: &mut R) {
+        assert!(
+            start <= end && end <= self.draw_pile.len(),
+            "invalid shuffle range {}..{} for a draw pile of {} cards",
+            start,
+            end,
+            self.draw_pile.len()
+        );
+
+        self.draw_pile[start..end].shuffle(rng);
+    }
+
+    pub fn cut(&mut self, position: usize) {
+        assert!(
+            position <= self.draw_pile.len(),
+            "cannot cut {} cards from a draw pile of {} cards",
+            position,
+            self.draw_pile.len()
+        );
+
+        self.draw_pile.rotate_right(position);
+    }
+
+    pub fn cut_random<R: Rng>(&mut self, rng: &mut R) {
+        if self.draw_pile.is_empty() {
+            return;
+        }
+
+        let position = rng.gen_range(1..=self.draw_pile.len());
+        self.cut(position);
+    }
+
+    pub fn shuffle_cut_shuffle(&mut self) {
+        self.shuffle_cut_shuffle_with_rng(&mut thread_rng());
+    }
+
+    pub fn shuffle_cut_shuffle_with_rng<R: Rng>(&mut self, rng: &mut R) {
+        self.draw_pile.shuffle(rng);
+        self.cut_random(rng);
+        self.draw_pile.shuffle(rng);
+    }
+
+    pub fn shuffle_into_draw<R: Rng>(&mut self, items: Vec<T>, rng: &mut R) {
+        self.draw_pile.shuffle(rng);
+        for x in items {
+            let index = rng.gen_range(0..=self.draw_pile.len());
+            self.draw_pile.insert(index, x);
+        }
+    }
+
+    pub fn sort_draw_then_shuffle_groups<K: Ord, F: FnMut(&T) -> K>(&mut self, mut key_fn: F) {
+        let mut keyed: Vec<(K, T)> = std::mem::take(&mut self.draw_pile)
+            .into_iter()
+            .map(|x| (key_fn(&x), x))
+            .collect();
+        keyed.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut rng = thread_rng();
+        for group in keyed.chunk_by_mut(|a, b| a.0 == b.0) {
+            group.shuffle(&mut rng);
+        }
+
+        self.draw_pile = keyed.into_iter().map(|(_, x)| x).collect();
+    }
+
+    pub fn split_by_predicate<F: FnMut(&T) -> bool>(self, mut f: F) -> (Deck<T>, Deck<T>) {
+        let (matching, others): (Vec<T>, Vec<T>) = self.draw_pile.into_iter().partition(|x| f(x));
+
+        let mut first = Self::new();
+        first.draw_pile = matching;
+        let mut second = Self::new();
+        second.draw_pile = others;
+
+        (first, second)
+    }
+
+    pub fn into_draw_vec(self) -> Vec<T> {
+        self.draw_pile
+    }
+
+    pub fn into_discard_vec(self) -> Vec<T> {
+        self.discard_pile
+    }
+
+    pub fn into_removed_vec(self) -> Vec<T> {
+        self.removed_pile
+    }
+
+    pub fn flatten_all_piles_into_vec(self) -> Vec<T> {
+        let mut all = self.draw_pile;
+        all.extend(self.discard_pile);
+        all.extend(self.removed_pile);
+        all
+    }
+
+    pub fn map_draw<U, F: FnMut(T) -> U>(self, mut f: F) -> Deck<U> {
+        Deck {
+            draw_pile: self.draw_pile.into_iter().map(&mut f).collect(),
+            discard_pile: self.discard_pile.into_iter().map(&mut f).collect(),
+            removed_pile: self.removed_pile.into_iter().map(&mut f).collect(),
+        }
+    }
+
+    fn iter_all_piles(&self) -> impl Iterator<Item = &T> {
+        self.draw_pile
+            .iter()
+            .chain(self.discard_pile.iter())
+            .chain(self.removed_pile.iter())
+    }
+
+    pub fn apply_to_draw_pile<F: FnOnce(&mut Vec<T>)>(&mut self, f: F) {
+        f(&mut self.draw_pile);
+    }
+
+    pub fn apply_to_discard_pile<F: FnOnce(&mut Vec<T>)>(&mut self, f: F) {
+        f(&mut self.discard_pile);
+    }
+
+    pub fn apply_to_removed_pile<F: FnOnce(&mut Vec<T>)>(&mut self, f: F) {
+        f(&mut self.removed_pile);
+    }
+
+    pub fn replace_draw_pile(&mut self, new_pile: Vec<T>) -> Vec<T> {
+        std::mem::replace(&mut self.draw_pile, new_pile)
+    }
+
+    pub fn replace_discard_pile(&mut self, new_pile: Vec<T>) -> Vec<T> {
+        std::mem::replace(&mut self.discard_pile, new_pile)
+    }
+
+    pub fn replace_removed_pile(&mut self, new_pile: Vec<T>) -> Vec<T> {
+        std::mem::replace(&mut self.removed_pile, new_pile)
+    }
+
+    pub fn clear_all_piles(&mut self) -> (usize, usize, usize) {
+        let sizes = self.pile_sizes();
+        self.draw_pile.clear();
+        self.discard_pile.clear();
+        self.removed_pile.clear();
+
+        sizes
+    }
+
+    pub fn compact(&mut self) {
+        self.draw_pile.shrink_to_fit();
+        self.discard_pile.shrink_to_fit();
+        self.removed_pile.shrink_to_fit();
+    }
+
+    pub fn reserve_draw(&mut self, additional: usize) {
+        self.draw_pile.reserve(additional);
+    }
+
+    pub fn reserve_discard(&mut self, additional: usize) {
+        self.discard_pile.reserve(additional);
+    }
+
+    pub fn reserve_removed(&mut self, additional: usize) {
+        self.removed_pile.reserve(additional);
+    }
+
+    pub fn capacity_draw(&self) -> usize {
+        self.draw_pile.capacity()
+    }
+
+    pub fn capacity_discard(&self) -> usize {
+        self.discard_pile.capacity()
+    }
+
+    pub fn capacity_removed(&self) -> usize {
+        self.removed_pile.capacity()
+    }
+}
+
+impl<T: Clone> Deck<T> {
+    pub fn begin_transaction(self) -> DeckTransaction<T> {
+        DeckTransaction::new(self)
+    }
+
+    pub fn random_sample_draw(&self, n: usize) -> Vec<T> {
+        let mut indices: Vec<usize> = (0..self.draw_pile.len()).collect();
+        let n = n.min(indices.len());
+        let (sample, _) = indices.partial_shuffle(&mut thread_rng(), n);
+
+        sample.iter().map(|i| self.draw_pile[*i].clone()).collect()
+    }
+
+    pub fn copy_top_n(&self, n: usize) -> Vec<T> {
+        self.peek_top_n(n).iter().rev().cloned().collect()
+    }
+
+    pub fn draw_pile_diff(&self, other: &Deck<T>) -> (Vec<T>, Vec<T>)
+    where
+        T: PartialEq,
+    {
+        let mut matched = vec![false; other.draw_pile.len()];
+        let mut only_mine = Vec::new();
+        for x in &self.draw_pile {
+            let found = other
+                .draw_pile
+                .iter()
+                .enumerate()
+                .position(|(i, y)| !matched[i] && y == x);
+            match found {
+                Some(i) => matched[i] = true,
+                None => only_mine.push(x.clone()),
+            }
+        }
+
+        let only_theirs = other
+            .draw_pile
+            .iter()
+            .zip(matched)
+            .filter(|(_, matched)| !matched)
+            .map(|(x, _)| x.clone())
+            .collect();
+
+        (only_mine, only_theirs)
+    }
+}
+
+impl<T: PartialEq> Deck<T> {
+    pub fn count_occurrences(&self, item: &T) -> usize {
+        self.draw_pile.iter().filter(|x| *x == item).count()
+    }
+
+    pub fn count_occurrences_in_discard(&self, item: &T) -> usize {
+        self.discard_pile.iter().filter(|x| *x == item).count()
+    }
+
+    pub fn count_occurrences_in_removed(&self, item: &T) -> usize {
+        self.removed_pile.iter().filter(|x| *x == item).count()
+    }
+
+    pub fn count_occurrences_total(&self, item: &T) -> usize {
+        self.iter_all_piles().filter(|x| *x == item).count()
+    }
+
+    pub fn contains_in_any_pile(&self, item: &T) -> bool {
+        self.iter_all_piles().any(|x| x == item)
+    }
+
+    pub fn draw_pile_positions_of(&self, item: &T) -> Vec<usize> {
+        Self::positions_of(&self.draw_pile, item)
+    }
+
+    pub fn discard_pile_positions_of(&self, item: &T) -> Vec<usize> {
+        Self::positions_of(&self.discard_pile, item)
+    }
+
+    pub fn removed_pile_positions_of(&self, item: &T) -> Vec<usize> {
+        Self::positions_of(&self.removed_pile, item)
+    }
+
+    fn positions_of(pile: &[T], item: &T) -> Vec<usize> {
+        pile.iter()
+            .enumerate()
+            .filter(|(_, x)| *x == item)
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+impl<T: Eq + Hash> Deck<T> {
+    pub fn unique_count(&self) -> usize {
+        self.draw_pile.iter().collect::<HashSet<_>>().len()
+    }
+
+    pub fn unique_count_discard(&self) -> usize {
+        self.discard_pile.iter().collect::<HashSet<_>>().len()
+    }
+
+    pub fn unique_count_removed(&self) -> usize {
+        self.removed_pile.iter().collect::<HashSet<_>>().len()
+    }
+
+    pub fn validate_no_duplicates(&self) -> bool {
+        let mut seen = HashSet::new();
+        self.iter_all_piles().all(|x| seen.insert(x))
+    }
+
+    pub fn dedup_across_piles(&mut self) -> Vec<T> {
+        let mut seen = HashSet::new();
+        let keep: Vec<Vec<bool>> = [&self.draw_pile, &self.discard_pile, &self.removed_pile]
+            .into_iter()
+            .map(|pile| pile.iter().map(|x| seen.insert(x)).collect())
+            .collect();
+
+        let mut eliminated = Vec::new();
+        let piles = [
+            &mut self.draw_pile,
+            &mut self.discard_pile,
+            &mut self.removed_pile,
+        ];
+        for (pile, keep) in piles.into_iter().zip(keep) {
+            for (x, keep) in std::mem::take(pile).into_iter().zip(keep) {
+                if keep {
+                    pile.push(x);
+                } else {
+                    eliminated.push(x);
+                }
+            }
+        }
+
+        eliminated
+    }
+
+    pub fn stats_with_unique(&self) -> DeckStatsWithUnique {
+        DeckStatsWithUnique {
+            stats: self.stats(),
+            unique_in_draw: self.unique_count(),
+            unique_in_discard: self.unique_count_discard(),
+        }
+    }
+}
+
+impl<T: Eq + Hash + Clone> Deck<T> {
+    pub fn histogram(&self) -> HashMap<T, usize> {
+        let mut histogram = HashMap::new();
+        Self::add_to_histogram(&mut histogram, &self.draw_pile);
+        histogram
+    }
+
+    pub fn histogram_discard(&self) -> HashMap<T, usize> {
+        let mut histogram = HashMap::new();
+        Self::add_to_histogram(&mut histogram, &self.discard_pile);
+        histogram
+    }
+
+    pub fn histogram_all_piles(&self) -> HashMap<T, usize> {
+        let mut histogram = HashMap::new();
+        for pile in [&self.draw_pile, &self.discard_pile, &self.removed_pile] {
+            Self::add_to_histogram(&mut histogram, pile);
+        }
+        histogram
+    }
+
+    pub fn find_duplicates(&self) -> Vec<T> {
+        let mut seen = HashSet::new();
+        let mut reported = HashSet::new();
+        self.iter_all_piles()
+            .filter(|x| !seen.insert(*x) && reported.insert(*x))
+            .cloned()
+            .collect()
+    }
+
+    fn add_to_histogram(histogram: &mut HashMap<T, usize>, pile: &[T]) {
+        for x in pile {
+            *histogram.entry(x.clone()).or_insert(0) += 1;
+        }
+    }
+}
+
+impl<T: Ord> Deck<T> {
+    pub fn sort_all_piles(&mut self) {
+        self.draw_pile.sort();
+        self.discard_pile.sort();
+        self.removed_pile.sort();
+    }
+
+    pub fn sort_all_piles_unstable(&mut self) {
+        self.draw_pile.sort_unstable();
+        self.discard_pile.sort_unstable();
+        self.removed_pile.sort_unstable();
+    }
+
+    pub fn min_in_draw(&self) -> Option<&T> {
+        self.draw_pile.iter().min()
+    }
+
+    pub fn max_in_draw(&self) -> Option<&T> {
+        self.draw_pile.iter().max()
+    }
+
+    pub fn draw_min(&mut self) -> Option<T> {
+        let (index, _) = self.draw_pile.iter().enumerate().min_by_key(|(_, x)| *x)?;
+        self.draw_at(index)
+    }
+
+    pub fn draw_max(&mut self) -> Option<T> {
+        let (index, _) = self.draw_pile.iter().enumerate().max_by_key(|(_, x)| *x)?;
+        self.draw_at(index)
+    }
+
+    pub fn draw_pile_eq_multiset(&self, other: &Deck<T>) -> bool {
+        if self.draw_pile.len() != other.draw_pile.len() {
+            return false;
+        }
+
+        let mut mine: Vec<&T> = self.draw_pile.iter().collect();
+        let mut theirs: Vec<&T> = other.draw_pile.iter().collect();
+        mine.sort_unstable();
+        theirs.sort_unstable();
+        mine == theirs
+    }
+
+    pub fn draw_pile_same_multiset(&self, other: &Deck<T>) -> bool {
+        self.draw_pile_eq_multiset(other)
+    }
+
+    pub fn drain_discard_sorted(&mut self) -> Vec<T> {
+        self.drain_discard_sorted_by(T::cmp)
+    }
+}
+
+impl<T> Default for Deck<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<Vec<T>> for Deck<T> {
+    fn from(draw_pile: Vec<T>) -> Self {
+        Self {
+            draw_pile,
+            discard_pile: Vec::<T>::new(),
+            removed_pile: Vec::<T>::new(),
+        }
+    }
+}
+
+impl<T: Display> Display for Deck<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (label, pile) in [
+            ("Draw pile", &self.draw_pile),
+            ("Discard pile", &self.discard_pile),
+            ("Removed pile", &self.removed_pile),
+        ] {
+            write!(f, "{}:", label)?;
+            if pile.is_empty() {
+                write!(f, " empty")?;
+            }
+            for (i, x) in pile.iter().rev().enumerate() {
+                let separator = if i == 0 { " " } else { ", " };
+                let marker = if i == 0 { " (top)" } else { "" };
+                write!(f, "{}{}{}", separator, x, marker)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Sum<T> for Deck<T> {
+    fn sum<I: Iterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.collect::<Vec<T>>())
+    }
+}
+
+impl<T> AsRef<[T]> for Deck<T> {
+    fn as_ref(&self) -> &[T] {
+        self.draw_pile.as_slice()
+    }
+}
+
+impl<T> AsMut<[T]> for Deck<T> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self.draw_pile.as_mut_slice()
+    }
+}
+
+impl<T> Index<usize> for Deck<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.draw_pile[index]
+    }
+}
+
+impl<T> IndexMut<usize> for Deck<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.draw_pile[index]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeckStats {
+    pub draw_count: usize,
+    pub discard_count: usize,
+    pub removed_count: usize,
+    pub total: usize,
+    pub draw_fraction: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeckStatsWithUnique {
+    pub stats: DeckStats,
+    pub unique_in_draw: usize,
+    pub unique_in_discard: usize,
+}
+
+pub struct ZipPilesIter<'a, T> {
+    draw: std::slice::Iter<'a, T>,
+    discard: std::slice::Iter<'a, T>,
+    removed: std::slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for ZipPilesIter<'a, T> {
+    type Item = (&'a T, Option<&'a T>, Option<&'a T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let draw = self.draw.next()?;
+        Some((draw, self.discard.next(), self.removed.next()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.draw.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for ZipPilesIter<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draw() {
+        let mut deck = Deck::<u8>::new();
+
+        assert!(!deck.can_draw());
+        assert_eq!(deck.draw_pile.len(), 0);
+        assert_eq!(deck.remaining(), 0);
+        assert_eq!(deck.draw_top(), None);
+        assert_eq!(deck.draw_bottom(), None);
+
+        deck.put_top(11);
+        assert_eq!(deck.draw_pile.len(), 1);
+        assert_eq!(deck.remaining(), 1);
+        assert!(deck.can_draw());
+
+        deck.put_top(7);
+        assert_eq!(deck.draw_pile.len(), 2);
+        assert_eq!(deck.remaining(), 2);
+        assert!(deck.can_draw());
+
+        deck.put_bottom(5);
+        assert_eq!(deck.draw_pile.len(), 3);
+        assert_eq!(deck.remaining(), 3);
+        assert!(deck.can_draw());
+
+        assert_eq!(deck.draw_top(), Some(7));
+        assert!(deck.can_draw());
+        assert_eq!(deck.draw_bottom(), Some(5));
+        assert!(deck.can_draw());
+        assert_eq!(deck.draw_bottom(), Some(11));
+
+        assert!(!deck.can_draw());
+        assert_eq!(deck.draw_top(), None);
+        assert_eq!(deck.draw_bottom(), None);
+    }
+
+    #[test]
+    fn test_weighted_draw() {
+        let mut deck = Deck::<u8>::new();
+        assert_eq!(deck.weighted_draw(&[]), None);
+
+        (0..5).for_each(|i| deck.put_top(i));
+        for _ in 0..1000 {
+            let mut copy = Deck::<u8>::new();
+            (0..5).for_each(|i| copy.put_top(i));
+            assert_eq!(copy.weighted_draw(&[0.0, 0.0, 1.0, 0.0, 0.0]), Some(2));
+            assert_eq!(copy.peek_draw_pile(), [0, 1, 3, 4]);
+        }
+
+        assert_eq!(deck.weighted_draw(&[0.0, 0.0, 0.0, 0.0, 1.0]), Some(4));
+        assert_eq!(deck.weighted_draw(&[1.0, 0.0, 0.0, 0.0]), Some(0));
+        assert_eq!(deck.remaining(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "weights must match the draw pile length")]
+    fn test_weighted_draw_wrong_length() {
+        let mut deck = Deck::<u8>::new();
+        deck.put_top(0);
+        deck.put_top(1);
+        deck.weighted_draw(&[1.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "weights must not be negative")]
+    fn test_weighted_draw_negative() {
+        let mut deck = Deck::<u8>::new();
+        deck.put_top(0);
+        deck.put_top(1);
+        deck.weighted_draw(&[1.0, -1.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "weights must be finite")]
+    fn test_weighted_draw_nan() {
+        let mut deck = Deck::from(vec![0_u8, 1]);
+        deck.weighted_draw(&[1.0, f64::NAN]);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one weight must be positive")]
+    fn test_weighted_draw_all_zero() {
+        let mut deck = Deck::from(vec![0_u8, 1]);
+        deck.weighted_draw(&[0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_random_sample_draw() {
+        let mut deck = Deck::<u8>::new();
+        assert!(deck.random_sample_draw(3).is_empty());
+
+        (0..10).for_each(|i| deck.put_top(i));
+        for n in 0..15 {
+            let mut sample = deck.random_sample_draw(n);
+            assert_eq!(sample.len(), n.min(10));
+            assert_eq!(deck.remaining(), 10);
+            assert!(sample.iter().all(|x| deck.peek_draw_pile().contains(x)));
+
+            sample.sort();
+            sample.dedup();
+            assert_eq!(sample.len(), n.min(10));
+        }
+        assert_eq!(deck.peek_draw_pile(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_shuffle_draw() {
+        let mut deck = Deck::<u8>::new();
+        deck.put_top(1);
+        deck.put_bottom(2);
+        for _ in 0..2 {
+            deck.put_top(0);
+            deck.put_bottom(0);
+        }
+
+        for _ in 0..10000 {
+            // just try long enough
+            deck.shuffle_draw();
+            if deck.draw_pile.last() == Some(&1) {
+                break;
+            }
+        }
+        assert_eq!(deck.draw_top(), Some(1));
+
+        for _ in 0..10000 {
+            // again
+            deck.shuffle_draw();
+            if deck.draw_pile.last() == Some(&2) {
+                break;
+            }
+        }
+        assert_eq!(deck.draw_top(), Some(2));
+    }
+
+    #[test]
+    fn test_shuffle_discard() {
+        let mut deck = Deck::<u8>::new();
+        deck.discard(0);
+        deck.discard(1);
+        assert_eq!(deck.peek_discard_pile(), [0, 1]);
+
+        for _ in 0..1000 {
+            // just try long enough
+            deck.shuffle_discard();
+            if deck.discard_pile.last() == Some(&0) {
+                break;
+            }
+        }
+
+        assert_eq!(deck.peek_discard_pile(), [1, 0]);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut deck = Deck::<u8>::new();
+        assert_eq!(deck.removed_pile.len(), 0);
+        deck.remove(3);
+        assert_eq!(deck.removed_pile.len(), 1);
+        deck.remove(8);
+        assert_eq!(deck.removed_pile.len(), 2);
+        assert_eq!(deck.peek_removed_pile(), [3, 8]);
+    }
+
+    #[test]
+    fn test_discard() {
+        let mut deck = Deck::<u8>::new();
+        assert_eq!(deck.discard_pile.len(), 0);
+        deck.discard(5);
+        assert_eq!(deck.discard_pile.len(), 1);
+        deck.discard(7);
+        assert_eq!(deck.discard_pile.len(), 2);
+        assert_eq!(deck.peek_discard_pile(), [5, 7]);
+    }
+
+    #[test]
+    fn test_put_sparse() {
+        sub_test_sparse_zero(50);
+
+        for deck_size in 0..60 {
+            for n_insert in 1..(deck_size + 1) {
+                sub_test_sparse(deck_size, n_insert);
+            }
+        }
+    }
+
+    fn sub_test_sparse_zero(initial_deck_size: usize) {
+        let mut deck = Deck::<usize>::new();
+        (0..initial_deck_size).for_each(|i| deck.put_top(i));
+
+        deck.put_sparse(Vec::new());
+        assert_eq!(deck.draw_pile.len(), initial_deck_size);
+        for i in 0..deck.draw_pile.len() {
+            assert_eq!(deck.draw_pile[i], i);
+        }
+    }
+
+    fn sub_test_sparse(initial_deck_size: usize, n_insert: usize) {
+        let mut deck = Deck::<usize>::new();
+        (0..initial_deck_size).for_each(|i| deck.put_top(i));
+
+        deck.put_sparse((initial_deck_size..initial_deck_size + n_insert).collect());
+        assert_eq!(deck.draw_pile.len(), initial_deck_size + n_insert);
+
+        let bucket_standard_size = initial_deck_size / n_insert + 1;
+        let mut carry = initial_deck_size % n_insert;
+        let mut start_counter: usize = 0;
+        let mut remaining = deck.draw_pile.as_slice();
+        let mut expected = initial_deck_size;
+
+        while !remaining.is_empty() {
+            let size = bucket_standard_size
+                + if carry > 0 {
+                    carry -= 1;
+                    1
+                } else {
+                    0
+                };
+
+            let (bucket, tail) = remaining.split_at(size);
+
+            // check bucket
+            assert_eq!(bucket.len(), size);
+            assert!(bucket.contains(&expected));
+
+            for (i, e) in bucket.iter().filter(|x| **x != expected).enumerate() {
+                assert_eq!(*e, start_counter + i);
+            }
+
+            // loop
+            remaining = tail;
+            start_counter += size - 1;
+            expected += 1;
+        }
+    }
+
+    #[test]
+    fn test_draw_at() {
+        let mut deck = Deck::<u8>::new();
+        assert_eq!(deck.draw_at(0), None);
+
+        (0..5).for_each(|i| deck.put_top(i));
+        assert_eq!(deck.draw_at(5), None);
+        assert_eq!(deck.draw_at(2), Some(2));
+        assert_eq!(deck.draw_at(0), Some(0));
+        assert_eq!(deck.draw_at(2), Some(4));
+        assert_eq!(deck.peek_draw_pile(), [1, 3]);
+    }
+
+    #[test]
+    fn test_draw_random_n() {
+        let mut deck = Deck::<u8>::new();
+        assert!(deck.draw_random_n(3).is_empty());
+
+        (0..10).for_each(|i| deck.put_top(i));
+        let mut drawn = deck.draw_random_n(4);
+        assert_eq!(drawn.len(), 4);
+        assert_eq!(deck.remaining(), 6);
+        assert!(drawn
+            .iter()
+            .all(|x| *x < 10 && !deck.peek_draw_pile().contains(x)));
+
+        drawn.extend(deck.draw_random_n(100));
+        assert_eq!(drawn.len(), 10);
+        assert!(!deck.can_draw());
+
+        drawn.sort();
+        assert_eq!(drawn, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_deal_round_robin() {
+        let mut deck = Deck::<u8>::new();
+        assert!(deck.deal_round_robin(0).is_empty());
+        assert_eq!(deck.deal_round_robin(2), [vec![], vec![]]);
+
+        (0..7).for_each(|i| deck.put_top(i));
+        assert!(deck.deal_round_robin(0).is_empty());
+        assert_eq!(deck.remaining(), 7);
+
+        let hands = deck.deal_round_robin(3);
+        assert_eq!(hands, [vec![6, 3, 0], vec![5, 2], vec![4, 1]]);
+        assert!(!deck.can_draw());
+    }
+
+    #[test]
+    fn test_deal_equal() {
+        let mut deck = Deck::<u8>::new();
+        (0..6).for_each(|i| deck.put_top(i));
+
+        assert_eq!(deck.deal_equal(4, 2), None);
+        assert_eq!(deck.deal_equal(usize::MAX, 2), None);
+        assert_eq!(deck.remaining(), 6);
+
+        assert_eq!(deck.deal_equal(2, 2), Some(vec![vec![5, 3], vec![4, 2]]));
+        assert_eq!(deck.peek_draw_pile(), [0, 1]);
+
+        assert_eq!(deck.deal_equal(1, 2), Some(vec![vec![1, 0]]));
+        assert!(!deck.can_draw());
+
+        assert_eq!(deck.deal_equal(3, 0), Some(vec![vec![], vec![], vec![]]));
+        assert_eq!(deck.deal_equal(1, 1), None);
+    }
+
+    #[test]
+    fn test_transfer_top_n_to() {
+        let mut deck = Deck::<u8>::new();
+        let mut other = Deck::<u8>::new();
+        (0..5).for_each(|i| deck.put_top(i));
+        other.put_top(10);
+
+        assert_eq!(deck.transfer_top_n_to(&mut other, 0), 0);
+        assert_eq!(deck.remaining(), 5);
+        assert_eq!(other.remaining(), 1);
+
+        assert_eq!(deck.transfer_top_n_to(&mut other, 2), 2);
+        assert_eq!(deck.peek_draw_pile(), [0, 1, 2]);
+        assert_eq!(other.peek_draw_pile(), [10, 3, 4]);
+        assert_eq!(other.draw_top(), Some(4));
+        other.put_top(4);
+
+        assert_eq!(deck.transfer_top_n_to(&mut other, 10), 3);
+        assert!(!deck.can_draw());
+        assert_eq!(other.peek_draw_pile(), [10, 3, 4, 0, 1, 2]);
+
+        assert_eq!(deck.transfer_top_n_to(&mut other, 1), 0);
+        assert_eq!(other.remaining(), 6);
+    }
+
+    #[test]
+    fn test_merge() {
+        assert_eq!(Deck::<u8>::merge(Vec::new()).remaining(), 0);
+
+        let mut first = Deck::<u8>::new();
+        (0..3).for_each(|i| first.put_top(i));
+        first.discard(10);
+        first.remove(20);
+
+        let mut second = Deck::<u8>::new();
+        (3..5).for_each(|i| second.put_top(i));
+        second.discard(11);
+        second.discard(12);
+
+        let merged = Deck::merge([first, Deck::new(), second]);
+        assert_eq!(merged.peek_draw_pile(), [0, 1, 2, 3, 4]);
+        assert_eq!(merged.peek_discard_pile(), [10, 11, 12]);
+        assert_eq!(merged.peek_removed_pile(), [20]);
+    }
+
+    #[test]
+    fn test_split_by_predicate() {
+        let mut deck = Deck::<u8>::new();
+        (0..10).for_each(|i| deck.put_top(i));
+        deck.discard(20);
+        deck.remove(30);
+
+        let (even, odd) = deck.split_by_predicate(|x| x % 2 == 0);
+        assert_eq!(even.peek_draw_pile(), [0, 2, 4, 6, 8]);
+        assert_eq!(odd.peek_draw_pile(), [1, 3, 5, 7, 9]);
+        for d in [&even, &odd] {
+            assert!(d.peek_discard_pile().is_empty());
+            assert!(d.peek_removed_pile().is_empty());
+        }
+
+        let (all, none) = even.split_by_predicate(|_| true);
+        assert_eq!(all.remaining(), 5);
+        assert_eq!(none.remaining(), 0);
+    }
+
+    #[test]
+    fn test_apply_to_piles() {
+        let mut deck = Deck::<u8>::new();
+        (0..6).for_each(|i| deck.put_top(i));
+        (0..4).for_each(|i| deck.discard(i));
+        (0..3).for_each(|i| deck.remove(i));
+
+        deck.apply_to_draw_pile(|pile| pile.retain(|x| x % 2 == 0));
+        assert_eq!(deck.remaining(), 3);
+        assert_eq!(deck.peek_draw_pile(), [0, 2, 4]);
+
+        deck.apply_to_discard_pile(|pile| pile.reverse());
+        assert_eq!(deck.peek_discard_pile(), [3, 2, 1, 0]);
+
+        deck.apply_to_removed_pile(|pile| pile.clear());
+        assert!(deck.peek_removed_pile().is_empty());
+    }
+
+    #[test]
+    fn test_unique_count() {
+        let mut deck = Deck::<u8>::new();
+        assert_eq!(deck.unique_count(), 0);
+        assert_eq!(deck.unique_count_discard(), 0);
+        assert_eq!(deck.unique_count_removed(), 0);
+
+        (0..5).for_each(|_| deck.put_top(7));
+        assert_eq!(deck.unique_count(), 1);
+
+        (0..5).for_each(|i| deck.discard(i));
+        assert_eq!(deck.unique_count_discard(), deck.peek_discard_pile().len());
+
+        [1, 2, 2, 3, 1, 1].into_iter().for_each(|i| deck.remove(i));
+        assert_eq!(deck.unique_count_removed(), 3);
+    }
+
+    #[test]
+    fn test_histogram() {
+        let mut deck = Deck::<u8>::new();
+        assert!(deck.histogram().is_empty());
+
+        [1, 2, 2, 3, 3, 3].into_iter().for_each(|i| deck.put_top(i));
+        [3, 4].into_iter().for_each(|i| deck.discard(i));
+        deck.remove(1);
+
+        let histogram = deck.histogram();
+        assert_eq!(histogram.values().sum::<usize>(), deck.remaining());
+        assert_eq!(histogram.len(), 3);
+        for (value, count) in &histogram {
+            let expected = deck.peek_draw_pile().iter().filter(|x| *x == value).count();
+            assert_eq!(*count, expected);
+        }
+
+        assert_eq!(deck.histogram_discard(), HashMap::from([(3, 1), (4, 1)]));
+        assert_eq!(
+            deck.histogram_all_piles(),
+            HashMap::from([(1, 2), (2, 2), (3, 4), (4, 1)])
+        );
+    }
+
+    #[test]
+    fn test_sort_all_piles() {
+        let mut deck = Deck::<u8>::new();
+        [5, 1, 4, 1].into_iter().for_each(|i| deck.put_top(i));
+        [9, 0, 3].into_iter().for_each(|i| deck.discard(i));
+        [2, 8].into_iter().for_each(|i| deck.remove(i));
+
+        deck.sort_all_piles();
+        assert_eq!(deck.peek_draw_pile(), [1, 1, 4, 5]);
+        assert_eq!(deck.peek_discard_pile(), [0, 3, 9]);
+        assert_eq!(deck.peek_removed_pile(), [2, 8]);
+
+        let mut deck = Deck::<u8>::new();
+        [3, 2, 1].into_iter().for_each(|i| deck.put_top(i));
+        [7, 7, 6].into_iter().for_each(|i| deck.discard(i));
+        [5, 4].into_iter().for_each(|i| deck.remove(i));
+
+        deck.sort_all_piles_unstable();
+        assert_eq!(deck.peek_draw_pile(), [1, 2, 3]);
+        assert_eq!(deck.peek_discard_pile(), [6, 7, 7]);
+        assert_eq!(deck.peek_removed_pile(), [4, 5]);
+    }
+
+    #[test]
+    fn test_validate_no_duplicates() {
+        let mut deck = Deck::<u8>::new();
+        assert!(deck.validate_no_duplicates());
+        assert!(deck.find_duplicates().is_empty());
+
+        (0..5).for_each(|i| deck.put_top(i));
+        (5..8).for_each(|i| deck.discard(i));
+        deck.remove(8);
+        assert!(deck.validate_no_duplicates());
+        assert!(deck.find_duplicates().is_empty());
+
+        deck.put_top(2);
+        assert!(!deck.validate_no_duplicates());
+        assert_eq!(deck.find_duplicates(), [2]);
+
+        let mut deck = Deck::<u8>::new();
+        (0..5).for_each(|i| deck.put_top(i));
+        deck.discard(3);
+        deck.remove(3);
+        deck.remove(0);
+        assert!(!deck.validate_no_duplicates());
+        assert_eq!(deck.find_duplicates(), [3, 0]);
+    }
+
+    #[test]
+    fn test_count_occurrences() {
+        let mut deck = Deck::<u8>::new();
+        assert_eq!(deck.count_occurrences(&1), 0);
+        assert_eq!(deck.count_occurrences_total(&1), 0);
+
+        [1, 2, 2, 3, 3, 3].into_iter().for_each(|i| deck.put_top(i));
+        [3, 4].into_iter().for_each(|i| deck.discard(i));
+        [1, 3].into_iter().for_each(|i| deck.remove(i));
+
+        assert_eq!(deck.count_occurrences(&0), 0);
+        assert_eq!(deck.count_occurrences(&1), 1);
+        assert_eq!(deck.count_occurrences(&3), 3);
+        assert_eq!(deck.count_occurrences_in_discard(&2), 0);
+        assert_eq!(deck.count_occurrences_in_discard(&4), 1);
+
+        for i in 0..6 {
+            assert_eq!(
+                deck.count_occurrences_total(&i),
+                deck.count_occurrences(&i)
+                    + deck.count_occurrences_in_discard(&i)
+                    + deck.count_occurrences_in_removed(&i)
+            );
+        }
+        assert_eq!(deck.count_occurrences_total(&3), 5);
+    }
+
+    #[test]
+    fn test_min_max() {
+        let mut deck = Deck::<u8>::new();
+        assert_eq!(deck.min_in_draw(), None);
+        assert_eq!(deck.max_in_draw(), None);
+        assert_eq!(deck.draw_min(), None);
+        assert_eq!(deck.draw_max(), None);
+
+        deck.put_top(4);
+        assert_eq!(deck.min_in_draw(), Some(&4));
+        assert_eq!(deck.max_in_draw(), Some(&4));
+        assert_eq!(deck.draw_max(), Some(4));
+        deck.put_top(4);
+        assert_eq!(deck.draw_min(), Some(4));
+        assert!(!deck.can_draw());
+
+        let original = [5, 1, 9, 3, 1, 9, 7];
+        original.into_iter().for_each(|i| deck.put_top(i));
+        assert_eq!(deck.min_in_draw(), Some(&1));
+        assert_eq!(deck.max_in_draw(), Some(&9));
+        assert_eq!(deck.remaining(), 7);
+
+        assert_eq!(deck.draw_max(), Some(9));
+        assert_eq!(deck.count_occurrences(&9), 1);
+        assert_eq!(deck.draw_min(), Some(1));
+        assert_eq!(deck.count_occurrences(&1), 1);
+        assert_eq!(deck.remaining(), 5);
+        assert!(is_subsequence(deck.peek_draw_pile(), &original));
+    }
+
+    fn is_subsequence<T: PartialEq>(sub: &[T], full: &[T]) -> bool {
+        let mut full = full.iter();
+        sub.iter().all(|x| full.any(|y| y == x))
+    }
+
+    #[test]
+    fn test_compact() {
+        let mut deck = Deck::<u8>::new();
+        (0..100).for_each(|i| deck.put_top(i));
+        (0..50).for_each(|i| deck.discard(i));
+        (0..50).for_each(|i| deck.remove(i));
+        while deck.remaining() > 10 {
+            deck.draw_top();
+        }
+        deck.discard_pile.clear();
+        deck.removed_pile.truncate(1);
+
+        let capacity = deck.capacity_draw();
+        deck.compact();
+        assert!(deck.capacity_draw() < capacity);
+        assert!(deck.capacity_draw() >= 10);
+        assert_eq!(deck.capacity_discard(), 0);
+        assert!(deck.capacity_removed() >= 1);
+        assert!(deck.capacity_removed() < 50);
+        assert_eq!(deck.peek_draw_pile(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_reserve() {
+        let mut deck = Deck::<u8>::new();
+        assert_eq!(deck.capacity_draw(), 0);
+        assert_eq!(deck.capacity_discard(), 0);
+        assert_eq!(deck.capacity_removed(), 0);
+
+        deck.reserve_draw(20);
+        deck.reserve_discard(10);
+        deck.reserve_removed(5);
+        assert!(deck.capacity_draw() >= 20);
+        assert!(deck.capacity_discard() >= 10);
+        assert!(deck.capacity_removed() >= 5);
+
+        let capacity = deck.capacity_draw();
+        let pointer = deck.draw_pile.as_ptr();
+        (0..20).for_each(|i| deck.put_top(i));
+        assert_eq!(deck.capacity_draw(), capacity);
+        assert_eq!(deck.draw_pile.as_ptr(), pointer);
+
+        let capacity = deck.capacity_discard();
+        (0..10).for_each(|i| deck.discard(i));
+        assert_eq!(deck.capacity_discard(), capacity);
+
+        let capacity = deck.capacity_removed();
+        (0..5).for_each(|i| deck.remove(i));
+        assert_eq!(deck.capacity_removed(), capacity);
+    }
+
+    #[test]
+    fn test_draw_min() {
+        let mut deck = Deck::<u8>::new();
+        let original = [6, 2, 8, 0, 5, 0, 3];
+        original.into_iter().for_each(|i| deck.put_top(i));
+
+        let expected_min = *deck.min_in_draw().unwrap();
+        assert_eq!(deck.draw_min(), Some(expected_min));
+        assert_eq!(deck.remaining(), original.len() - 1);
+        assert!(is_subsequence(deck.peek_draw_pile(), &original));
+
+        assert_eq!(deck.draw_min(), Some(0));
+        assert_eq!(deck.peek_draw_pile(), [6, 2, 8, 5, 3]);
+        assert_eq!(deck.draw_min(), Some(2));
+        assert_eq!(deck.peek_draw_pile(), [6, 8, 5, 3]);
+    }
+
+    #[test]
+    fn test_draw_max() {
+        let mut deck = Deck::<u8>::new();
+        assert_eq!(deck.draw_max(), None);
+
+        deck.put_top(3);
+        assert_eq!(deck.draw_max(), Some(3));
+        assert!(!deck.can_draw());
+        assert_eq!(deck.draw_max(), None);
+
+        let original = [9, 2, 9, 4, 9, 1];
+        original.into_iter().for_each(|i| deck.put_top(i));
+
+        assert_eq!(deck.draw_max(), Some(9));
+        assert_eq!(deck.remaining(), original.len() - 1);
+        assert_eq!(deck.count_occurrences(&9), 2);
+        assert!(is_subsequence(deck.peek_draw_pile(), &original));
+
+        assert_eq!(deck.draw_max(), Some(9));
+        assert_eq!(deck.draw_max(), Some(9));
+        assert_eq!(deck.peek_draw_pile(), [2, 4, 1]);
+    }
+
+    #[test]
+    fn test_peek_at() {
+        let mut deck = Deck::<u8>::new();
+        assert_eq!(deck.peek_at(0), None);
+        assert_eq!(deck.peek_at_discard(0), None);
+        assert_eq!(deck.peek_at_removed(0), None);
+
+        (0..3).for_each(|i| deck.put_top(i));
+        (10..14).for_each(|i| deck.discard(i));
+        (20..22).for_each(|i| deck.remove(i));
+
+        assert_eq!(deck.peek_at(0), Some(&0));
+        assert_eq!(deck.peek_at(2), Some(&2));
+        assert_eq!(deck.peek_at(3), None);
+
+        assert_eq!(deck.peek_at_discard(0), Some(&10));
+        assert_eq!(deck.peek_at_discard(3), Some(&13));
+        assert_eq!(deck.peek_at_discard(4), None);
+
+        assert_eq!(deck.peek_at_removed(0), Some(&20));
+        assert_eq!(deck.peek_at_removed(1), Some(&21));
+        assert_eq!(deck.peek_at_removed(2), None);
+    }
+
+    #[test]
+    fn test_shuffle_seeded() {
+        let build = || {
+            let mut deck = Deck::<u8>::new();
+            (0..52).for_each(|i| deck.put_top(i));
+            (0..52).for_each(|i| deck.discard(i));
+            (0..52).for_each(|i| deck.remove(i));
+            deck
+        };
+
+        let mut first = build();
+        let mut second = build();
+        first.shuffle_draw_seeded(42);
+        second.shuffle_draw_seeded(42);
+        assert_eq!(first.peek_draw_pile(), second.peek_draw_pile());
+        assert_ne!(first.peek_draw_pile(), build().peek_draw_pile());
+
+        first.shuffle_discard_seeded(7);
+        second.shuffle_discard_seeded(7);
+        assert_eq!(first.peek_discard_pile(), second.peek_discard_pile());
+
+        first.shuffle_removed_seeded(3);
+        second.shuffle_removed_seeded(3);
+        assert_eq!(first.peek_removed_pile(), second.peek_removed_pile());
+
+        let mut other = build();
+        other.shuffle_draw_seeded(43);
+        other.shuffle_discard_seeded(8);
+        other.shuffle_removed_seeded(4);
+        assert_ne!(first.peek_draw_pile(), other.peek_draw_pile());
+        assert_ne!(first.peek_discard_pile(), other.peek_discard_pile());
+        assert_ne!(first.peek_removed_pile(), other.peek_removed_pile());
+
+        let mut golden = Deck::from((0..10).collect::<Vec<u8>>());
+        golden.shuffle_draw_seeded(42);
+        assert_eq!(golden.peek_draw_pile(), &vec![0, 3, 5, 7, 9, 4, 8, 1, 6, 2]);
+    }
+
+    #[test]
+    fn test_riffle_shuffle() {
+        let mut deck = Deck::<u8>::new();
+        deck.riffle_shuffle();
+        assert!(!deck.can_draw());
+
+        (0..52).for_each(|i| deck.put_top(i));
+        for _ in 0..100 {
+            let mut copy = Deck::<u8>::new();
+            (0..52).for_each(|i| copy.put_top(i));
+            copy.riffle_shuffle();
+
+            // a single riffle interleaves two increasing packets
+            let pile = copy.peek_draw_pile();
+            assert!((0..=52).any(|cut| {
+                let left: Vec<_> = pile.iter().filter(|x| **x < cut).collect();
+                let right: Vec<_> = pile.iter().filter(|x| **x >= cut).collect();
+                left.windows(2).all(|w| w[0] < w[1]) && right.windows(2).all(|w| w[0] < w[1])
+            }));
+
+            let mut sorted = pile.to_vec();
+            sorted.sort();
+            assert_eq!(sorted, deck.peek_draw_pile());
+        }
+
+        let mut first = Deck::<u8>::new();
+        let mut second = Deck::<u8>::new();
+        (0..52).for_each(|i| first.put_top(i));
+        (0..52).for_each(|i| second.put_top(i));
+        first.riffle_shuffle_with_rng(&mut ChaCha8Rng::seed_from_u64(5));
+        second.riffle_shuffle_with_rng(&mut ChaCha8Rng::seed_from_u64(5));
+        assert_eq!(first.peek_draw_pile(), second.peek_draw_pile());
+        assert_ne!(first.peek_draw_pile(), deck.peek_draw_pile());
+    }
+
+    #[test]
+    fn test_overhand_shuffle() {
+        let mut deck = Deck::<u8>::new();
+        deck.overhand_shuffle();
+        assert!(!deck.can_draw());
+
+        (0..52).for_each(|i| deck.put_top(i));
+        deck.overhand_shuffle();
+        assert_eq!(deck.remaining(), 52);
+
+        let mut rng = ChaCha8Rng::seed_from_u64(11);
+        for _ in 0..100 {
+            let mut copy = Deck::<u8>::new();
+            (0..52).for_each(|i| copy.put_top(i));
+            copy.overhand_shuffle_with_rng(&mut rng);
+
+            // packets keep their inner order and end up in reverse order
+            let pile = copy.peek_draw_pile();
+            let packet_starts: Vec<_> = (0..pile.len())
+                .filter(|i| *i == 0 || pile[*i] != pile[*i - 1] + 1)
+                .map(|i| pile[i])
+                .collect();
+            assert!(packet_starts.len() > 1);
+            assert!(packet_starts.windows(2).all(|w| w[0] > w[1]));
+            assert_eq!(packet_starts.last(), Some(&0));
+        }
+
+        let mut first = Deck::<u8>::new();
+        let mut second = Deck::<u8>::new();
+        (0..52).for_each(|i| first.put_top(i));
+        (0..52).for_each(|i| second.put_top(i));
+        first.overhand_shuffle_with_rng(&mut ChaCha8Rng::seed_from_u64(3));
+        second.overhand_shuffle_with_rng(&mut ChaCha8Rng::seed_from_u64(3));
+        assert_eq!(first.peek_draw_pile(), second.peek_draw_pile());
+    }
+
+    #[test]
+    fn test_from() {
+        let deck = Deck::from(vec![1_u8, 2, 3]);
+        assert_eq!(deck.peek_draw_pile(), [1, 2, 3]);
+        assert!(deck.peek_discard_pile().is_empty());
+        assert!(deck.peek_removed_pile().is_empty());
+    }
+
+    #[test]
+    fn test_from_seed_and_items() {
+        let items: Vec<u8> = (0..52).collect();
+        let first = Deck::from_seed_and_items(9, items.clone());
+        let second = Deck::from_seed_and_items(9, items.clone());
+        assert_eq!(first, second);
+        assert_ne!(first, Deck::from_seed_and_items(10, items.clone()));
+
+        let mut expected = Deck::from(items);
+        expected.shuffle_draw_seeded(9);
+        assert_eq!(first, expected);
+
+        let golden = Deck::from_seed_and_items(9, (0..10).collect::<Vec<u8>>());
+        assert_eq!(golden.peek_draw_pile(), &vec![0, 8, 9, 4, 3, 2, 7, 6, 1, 5]);
+    }
+
+    #[test]
+    fn test_sieve_draw() {
+        let mut deck = Deck::<u8>::new();
+        deck.sieve_draw(&[false]);
+        assert!(!deck.can_draw());
+        assert!(deck.peek_discard_pile().is_empty());
+
+        (0..7).for_each(|i| deck.put_top(i));
+        deck.sieve_draw(&[]);
+        assert_eq!(deck.remaining(), 7);
+
+        deck.discard(10);
+        deck.sieve_draw(&[true, false]);
+        assert_eq!(deck.peek_draw_pile(), [0, 2, 4, 6]);
+        assert_eq!(deck.peek_discard_pile(), [10, 1, 3, 5]);
+
+        deck.sieve_draw(&[false, true, true]);
+        assert_eq!(deck.peek_draw_pile(), [2, 4]);
+        assert_eq!(deck.peek_discard_pile(), [10, 1, 3, 5, 0, 6]);
+
+        deck.sieve_draw(&[true]);
+        assert_eq!(deck.peek_draw_pile(), [2, 4]);
+    }
+
+    #[test]
+    fn test_drain_while_into_other() {
+        let mut deck = Deck::<u8>::new();
+        let mut other = Deck::<u8>::new();
+        assert_eq!(deck.drain_while_into_other(&mut other, |_| true), 0);
+
+        [9, 8, 1, 2, 4, 6].into_iter().for_each(|i| deck.put_top(i));
+        other.put_top(10);
+
+        assert_eq!(deck.drain_while_into_other(&mut other, |x| x % 2 == 1), 0);
+        assert_eq!(deck.remaining(), 6);
+
+        assert_eq!(deck.drain_while_into_other(&mut other, |x| x % 2 == 0), 3);
+        assert_eq!(deck.peek_draw_pile(), [9, 8, 1]);
+        assert_eq!(other.peek_draw_pile(), [10, 6, 4, 2]);
+
+        assert_eq!(deck.drain_while_into_other(&mut other, |_| true), 3);
+        assert!(!deck.can_draw());
+        assert_eq!(other.peek_draw_pile(), [10, 6, 4, 2, 1, 8, 9]);
+    }
+
+    #[test]
+    fn test_pop_discard_removed() {
+        let mut deck = Deck::<u8>::new();
+        assert_eq!(deck.pop_discard(), None);
+        assert_eq!(deck.pop_removed(), None);
+
+        deck.put_top(0);
+        (1..4).for_each(|i| deck.discard(i));
+        (4..6).for_each(|i| deck.remove(i));
+
+        assert_eq!(deck.pop_discard(), Some(3));
+        assert_eq!(deck.peek_discard_pile(), [1, 2]);
+        assert_eq!(deck.pop_removed(), Some(5));
+        assert_eq!(deck.peek_removed_pile(), [4]);
+        assert_eq!(deck.pop_removed(), Some(4));
+        assert_eq!(deck.pop_removed(), None);
+        assert_eq!(deck.peek_draw_pile(), [0]);
+    }
+
+    #[test]
+    fn test_mill() {
+        let mut deck = Deck::<u8>::new();
+        assert_eq!(deck.mill(3), 0);
+
+        (0..5).for_each(|i| deck.put_top(i));
+        deck.discard(10);
+
+        assert_eq!(deck.mill(0), 0);
+        assert_eq!(deck.mill(2), 2);
+        assert_eq!(deck.peek_draw_pile(), [0, 1, 2]);
+        assert_eq!(deck.peek_discard_pile(), [10, 4, 3]);
+
+        assert_eq!(deck.mill(10), 3);
+        assert!(!deck.can_draw());
+        assert_eq!(deck.peek_discard_pile(), [10, 4, 3, 2, 1, 0]);
+        assert_eq!(deck.peek_discard_pile().len(), 6);
+    }
+
+    #[test]
+    fn test_extend_discard_removed() {
+        let mut deck = Deck::<u8>::new();
+        deck.put_top(0);
+        deck.discard(1);
+        deck.remove(2);
+
+        deck.extend_discard(Vec::new());
+        assert_eq!(deck.discard_count(), 1);
+
+        deck.extend_discard(3..6);
+        assert_eq!(deck.discard_count(), 4);
+        assert_eq!(deck.peek_discard_pile(), [1, 3, 4, 5]);
+
+        deck.extend_removed([8, 7]);
+        assert_eq!(deck.removed_count(), 3);
+        assert_eq!(deck.peek_removed_pile(), [2, 8, 7]);
+
+        assert_eq!(deck.peek_draw_pile(), [0]);
+    }
+
+    #[test]
+    fn test_peek_n() {
+        let mut deck = Deck::<u8>::new();
+        assert!(deck.peek_top_n(2).is_empty());
+        assert!(deck.peek_discard_n(2).is_empty());
+        assert!(deck.peek_removed_n(2).is_empty());
+
+        (0..5).for_each(|i| deck.put_top(i));
+        (10..14).for_each(|i| deck.discard(i));
+        (20..22).for_each(|i| deck.remove(i));
+
+        assert!(deck.peek_top_n(0).is_empty());
+        assert_eq!(deck.peek_top_n(2), [3, 4]);
+        assert_eq!(deck.peek_discard_n(3), [11, 12, 13]);
+        assert_eq!(deck.peek_discard_n(10), [10, 11, 12, 13]);
+        assert_eq!(deck.peek_removed_n(1), [21]);
+        assert_eq!(deck.peek_removed_n(2), [20, 21]);
+
+        assert_eq!(deck.remaining(), 5);
+        assert_eq!(deck.discard_count(), 4);
+        assert_eq!(deck.removed_count(), 2);
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        let mut deck = Deck::<u8>::new();
+        assert_eq!(deck.iter_draw_mut().count(), 0);
+
+        (0..3).for_each(|i| deck.put_top(i));
+        (10..13).for_each(|i| deck.discard(i));
+        (20..23).for_each(|i| deck.remove(i));
+
+        deck.iter_draw_mut().for_each(|x| *x += 1);
+        assert_eq!(deck.peek_draw_pile(), [1, 2, 3]);
+
+        deck.iter_discard_mut().for_each(|x| *x -= 1);
+        assert_eq!(deck.peek_discard_pile(), [9, 10, 11]);
+
+        deck.iter_removed_mut().for_each(|x| *x *= 2);
+        assert_eq!(deck.peek_removed_pile(), [40, 42, 44]);
+    }
+
+    #[test]
+    fn test_hash() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::Hasher;
+
+        let hash = |deck: &Deck<u8>| {
+            let mut hasher = DefaultHasher::new();
+            deck.hash(&mut hasher);
+            hasher.finish()
+        };
+        let build = || {
+            let mut deck = Deck::<u8>::new();
+            (0..3).for_each(|i| deck.put_top(i));
+            deck.discard(3);
+            deck.remove(4);
+            deck
+        };
+
+        assert_eq!(build(), build());
+        assert_eq!(hash(&build()), hash(&build()));
+
+        let mut draw = build();
+        draw.put_top(5);
+        let mut discard = build();
+        discard.discard(5);
+        let mut removed = build();
+        removed.remove(5);
+        let mut moved = build();
+        let x = moved.pop_discard().unwrap();
+        moved.remove(x);
+
+        let hashes: HashSet<u64> = [&build(), &draw, &discard, &removed, &moved]
+            .into_iter()
+            .map(hash)
+            .collect();
+        assert_eq!(hashes.len(), 5);
+
+        let decks: HashSet<Deck<u8>> = [build(), build(), draw].into_iter().collect();
+        assert_eq!(decks.len(), 2);
+    }
+
+    #[test]
+    fn test_display() {
+        let mut deck = Deck::<u32>::new();
+        assert_eq!(
+            deck.to_string(),
+            "Draw pile: empty\nDiscard pile: empty\nRemoved pile: empty\n"
+        );
+
+        (1..4).for_each(|i| deck.put_top(i));
+        deck.discard(10);
+        deck.discard(11);
+        deck.remove(20);
+
+        let output = deck.to_string();
+        assert!(output.contains("Draw pile"));
+        assert!(output.contains("Discard pile"));
+        assert!(output.contains("Removed pile"));
+        for x in [1, 2, 3, 10, 11, 20] {
+            assert!(output.contains(&x.to_string()));
+        }
+        assert_eq!(
+            output,
+            "Draw pile: 3 (top), 2, 1\nDiscard pile: 11 (top), 10\nRemoved pile: 20 (top)\n"
+        );
+    }
+
+    #[test]
+    fn test_index() {
+        let mut deck = Deck::<u8>::new();
+        (0..5).for_each(|i| deck.put_top(i * 10));
+
+        assert_eq!(deck[0], 0);
+        assert_eq!(deck[4], 40);
+
+        deck[0] = 1;
+        deck[4] += 1;
+        assert_eq!(deck.peek_draw_pile(), [1, 10, 20, 30, 41]);
+        assert_eq!(deck.draw_top(), Some(41));
+        assert_eq!(deck.draw_bottom(), Some(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_index_out_of_bounds() {
+        let mut deck = Deck::<u8>::new();
+        deck.put_top(0);
+        let _ = deck[1];
+    }
+
+    #[test]
+    fn test_into_vec() {
+        let build = || {
+            let mut deck = Deck::<u8>::new();
+            (0..3).for_each(|i| deck.put_top(i));
+            deck.put_bottom(9);
+            deck.discard(3);
+            deck.discard(4);
+            deck.remove(5);
+            deck
+        };
+
+        assert_eq!(build().into_draw_vec(), [9, 0, 1, 2]);
+        assert_eq!(build().into_discard_vec(), [3, 4]);
+        assert_eq!(build().into_removed_vec(), [5]);
+        assert!(Deck::<u8>::new().into_draw_vec().is_empty());
+    }
+
+    #[test]
+    fn test_flatten_all_piles_into_vec() {
+        let mut deck = Deck::<u8>::new();
+        assert_eq!(deck.total_count(), 0);
+
+        (0..4).for_each(|i| deck.put_top(i));
+        (4..6).for_each(|i| deck.discard(i));
+        (6..9).for_each(|i| deck.remove(i));
+        assert_eq!(deck.total_count(), 9);
+
+        let total = deck.total_count();
+        let all = deck.flatten_all_piles_into_vec();
+        assert_eq!(all.len(), total);
+        assert_eq!(all[..4], [0, 1, 2, 3]);
+        assert_eq!(all[4..6], [4, 5]);
+        assert_eq!(all[6..], [6, 7, 8]);
+    }
+
+    #[test]
+    fn test_put_sparse_even() {
+        let mut deck = Deck::<usize>::new();
+        deck.put_sparse_even(Vec::new());
+        assert!(!deck.can_draw());
+
+        deck.put_sparse_even(vec![100, 101]);
+        assert_eq!(deck.peek_draw_pile(), [100, 101]);
+
+        for initial_deck_size in 0..30 {
+            for n in 1..10 {
+                let mut deck = Deck::<usize>::new();
+                (0..initial_deck_size).for_each(|i| deck.put_top(i));
+                deck.put_sparse_even((100..100 + n).collect());
+
+                let len = initial_deck_size + n;
+                assert_eq!(deck.remaining(), len);
+                for i in 0..n {
+                    assert_eq!(deck[i * len / n], 100 + i);
+                }
+                let others: Vec<_> = deck.peek_draw_pile().iter().filter(|x| **x < 100).collect();
+                assert!(others.iter().enumerate().all(|(i, x)| **x == i));
+            }
+        }
+
+        let mut deck = Deck::<usize>::new();
+        (0..6).for_each(|i| deck.put_top(i));
+        deck.put_sparse_even(vec![100, 101, 102]);
+        assert_eq!(deck.peek_draw_pile(), [100, 0, 1, 101, 2, 3, 102, 4, 5]);
+    }
+
+    #[test]
+    fn test_positions_of() {
+        let mut deck = Deck::<u8>::new();
+        assert!(deck.draw_pile_positions_of(&1).is_empty());
+
+        [1, 2, 1, 3, 1].into_iter().for_each(|i| deck.put_top(i));
+        [2, 2].into_iter().for_each(|i| deck.discard(i));
+        [3, 1].into_iter().for_each(|i| deck.remove(i));
+
+        assert_eq!(deck.draw_pile_positions_of(&1), [0, 2, 4]);
+        assert_eq!(deck.draw_pile_positions_of(&3), [3]);
+        assert!(deck.draw_pile_positions_of(&4).is_empty());
+        assert_eq!(deck.discard_pile_positions_of(&2), [0, 1]);
+        assert!(deck.discard_pile_positions_of(&1).is_empty());
+        assert_eq!(deck.removed_pile_positions_of(&1), [1]);
+    }
+
+    #[test]
+    fn test_shuffle_between_indices() {
+        let mut deck = Deck::<u8>::new();
+        deck.shuffle_between_indices(0, 0);
+
+        (0..20).for_each(|i| deck.put_top(i));
+        for _ in 0..100 {
+            deck.shuffle_between_indices(5, 15);
+            assert_eq!(deck.peek_draw_pile()[..5], [0, 1, 2, 3, 4]);
+            assert_eq!(deck.peek_draw_pile()[15..], [15, 16, 17, 18, 19]);
+
+            let mut middle = deck.peek_draw_pile()[5..15].to_vec();
+            middle.sort();
+            assert_eq!(middle, (5..15).collect::<Vec<_>>());
+        }
+
+        let mut first = Deck::from((0..20).collect::<Vec<u8>>());
+        let mut second = Deck::from((0..20).collect::<Vec<u8>>());
+        first.shuffle_range_with_rng(0, 20, &mut ChaCha8Rng::seed_from_u64(1));
+        second.shuffle_range_with_rng(0, 20, &mut ChaCha8Rng::seed_from_u64(1));
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid shuffle range")]
+    fn test_shuffle_between_indices_reversed() {
+        let mut deck = Deck::from(vec![0_u8, 1, 2]);
+        deck.shuffle_between_indices(2, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid shuffle range")]
+    fn test_shuffle_between_indices_out_of_range() {
+        let mut deck = Deck::from(vec![0_u8, 1, 2]);
+        deck.shuffle_between_indices(1, 4);
+    }
+
+    #[test]
+    fn test_remaining_fraction() {
+        let mut deck = Deck::<u8>::new();
+        assert_eq!(deck.remaining_fraction(), 0.0);
+
+        (0..4).for_each(|i| deck.put_top(i));
+        assert_eq!(deck.remaining_fraction(), 1.0);
+
+        deck.mill(1);
+        assert_eq!(deck.remaining_fraction(), 0.75);
+
+        let x = deck.draw_top().unwrap();
+        deck.remove(x);
+        assert_eq!(deck.remaining_fraction(), 0.5);
+
+        deck.mill(2);
+        assert_eq!(deck.remaining_fraction(), 0.0);
+        assert!((0.0..=1.0).contains(&deck.remaining_fraction()));
+    }
+
+    #[test]
+    fn test_shuffle_draw_n_times() {
+        let ordered = Deck::from((0..52).collect::<Vec<u8>>());
+
+        let mut deck = ordered.clone();
+        deck.shuffle_draw_n_times(0);
+        assert_eq!(deck, ordered);
+        deck.shuffle_draw_n_times_with_rng(0, &mut ChaCha8Rng::seed_from_u64(2));
+        assert_eq!(deck, ordered);
+
+        let mut expected = ordered.clone();
+        expected.shuffle_draw_seeded(2);
+        deck.shuffle_draw_n_times_with_rng(1, &mut ChaCha8Rng::seed_from_u64(2));
+        assert_eq!(deck, expected);
+
+        let mut first = ordered.clone();
+        let mut second = ordered.clone();
+        first.shuffle_draw_n_times_with_rng(3, &mut ChaCha8Rng::seed_from_u64(4));
+        second.shuffle_draw_n_times_with_rng(3, &mut ChaCha8Rng::seed_from_u64(4));
+        assert_eq!(first, second);
+
+        deck.shuffle_draw_n_times(5);
+        deck.sort_all_piles();
+        assert_eq!(deck, ordered);
+    }
+
+    #[test]
+    fn test_cut() {
+        let mut deck = Deck::<u8>::new();
+        deck.cut(0);
+        assert!(!deck.can_draw());
+
+        (0..5).for_each(|i| deck.put_top(i));
+        deck.cut(2);
+        assert_eq!(deck.peek_draw_pile(), [3, 4, 0, 1, 2]);
+        deck.cut(5);
+        assert_eq!(deck.peek_draw_pile(), [3, 4, 0, 1, 2]);
+        deck.cut(0);
+        assert_eq!(deck.peek_draw_pile(), [3, 4, 0, 1, 2]);
+        deck.cut(3);
+        assert_eq!(deck.peek_draw_pile(), [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot cut")]
+    fn test_cut_too_deep() {
+        let mut deck = Deck::from(vec![0_u8, 1, 2]);
+        deck.cut(4);
+    }
+
+    #[test]
+    fn test_shuffle_cut_shuffle() {
+        let mut deck = Deck::<u8>::new();
+        deck.shuffle_cut_shuffle();
+        assert!(!deck.can_draw());
+
+        let ordered = Deck::from((0..52).collect::<Vec<u8>>());
+        let mut first = ordered.clone();
+        let mut second = ordered.clone();
+        first.shuffle_cut_shuffle_with_rng(&mut ChaCha8Rng::seed_from_u64(6));
+        second.shuffle_cut_shuffle_with_rng(&mut ChaCha8Rng::seed_from_u64(6));
+        assert_eq!(first, second);
+        assert_eq!(first.remaining(), 52);
+
+        let mut deck = ordered.clone();
+        deck.shuffle_cut_shuffle();
+        assert_eq!(deck.remaining(), 52);
+        deck.sort_all_piles();
+        assert_eq!(deck, ordered);
+    }
+
+    #[test]
+    fn test_filter_draw_into_discard() {
+        let mut deck = Deck::<u8>::new();
+        assert_eq!(deck.filter_draw_into_discard(|_| false), 0);
+
+        (0..8).for_each(|i| deck.put_top(i));
+        deck.discard(10);
+
+        assert_eq!(deck.filter_draw_into_discard(|_| true), 0);
+        assert_eq!(deck.remaining(), 8);
+
+        assert_eq!(deck.filter_draw_into_discard(|x| x % 3 != 0), 3);
+        assert_eq!(deck.peek_draw_pile(), [1, 2, 4, 5, 7]);
+        assert_eq!(deck.peek_discard_pile(), [10, 0, 3, 6]);
+
+        assert_eq!(deck.filter_draw_into_discard(|_| false), 5);
+        assert!(!deck.can_draw());
+        assert_eq!(deck.discard_count(), 9);
+    }
+
+    #[test]
+    fn test_move_all_draw_to_other() {
+        let mut deck = Deck::<u8>::new();
+        let mut other = Deck::<u8>::new();
+        deck.move_all_draw_to_other(&mut other);
+        assert!(!other.can_draw());
+
+        (0..4).for_each(|i| deck.put_top(i));
+        deck.discard(10);
+        deck.remove(20);
+        other.put_top(9);
+        other.discard(11);
+
+        deck.move_all_draw_to_other(&mut other);
+        assert!(!deck.can_draw());
+        assert_eq!(other.peek_draw_pile(), [9, 0, 1, 2, 3]);
+        assert_eq!(deck.peek_discard_pile(), [10]);
+        assert_eq!(deck.peek_removed_pile(), [20]);
+        assert_eq!(other.peek_discard_pile(), [11]);
+        assert_eq!(deck.total_count() + other.total_count(), 8);
+
+        let mut expected = Deck::<u8>::new();
+        let mut copy = other.clone();
+        copy.transfer_top_n_to(&mut expected, usize::MAX);
+        other.move_all_draw_to_other(&mut deck);
+        assert_eq!(deck.peek_draw_pile(), expected.peek_draw_pile());
+    }
+
+    #[test]
+    fn test_copy_top_n() {
+        let mut deck = Deck::<u8>::new();
+        assert!(deck.copy_top_n(3).is_empty());
+
+        (0..5).for_each(|i| deck.put_top(i));
+        assert!(deck.copy_top_n(0).is_empty());
+        assert_eq!(deck.copy_top_n(3), [4, 3, 2]);
+        assert_eq!(deck.copy_top_n(10), [4, 3, 2, 1, 0]);
+
+        let copy = deck.copy_top_n(2);
+        deck.mill(5);
+        assert_eq!(copy, [4, 3]);
+    }
+
+    #[test]
+    fn test_draw_pile_eq_multiset() {
+        let empty = Deck::<u8>::new();
+        assert!(empty.draw_pile_eq_multiset(&Deck::new()));
+
+        let deck = Deck::from(vec![1_u8, 2, 2, 3]);
+        let mut shuffled = deck.clone();
+        shuffled.shuffle_draw();
+        shuffled.discard(7);
+        assert!(deck.draw_pile_eq_multiset(&shuffled));
+        assert!(deck.draw_pile_eq_multiset(&Deck::from(vec![3, 2, 1, 2])));
+
+        assert!(!deck.draw_pile_eq_multiset(&empty));
+        assert!(!deck.draw_pile_eq_multiset(&Deck::from(vec![1, 2, 3, 3])));
+        assert!(!deck.draw_pile_eq_multiset(&Deck::from(vec![1, 2, 2, 3, 4])));
+    }
+
+    #[test]
+    fn test_contains_in_any_pile() {
+        let mut deck = Deck::<u8>::new();
+        assert!(!deck.contains_in_any_pile(&0));
+
+        deck.put_top(1);
+        deck.discard(2);
+        deck.remove(3);
+        deck.put_top(4);
+        deck.discard(4);
+        deck.remove(4);
+
+        assert!(deck.contains_in_any_pile(&1));
+        assert!(deck.contains_in_any_pile(&2));
+        assert!(deck.contains_in_any_pile(&3));
+        assert!(deck.contains_in_any_pile(&4));
+        assert!(!deck.contains_in_any_pile(&5));
+    }
+
+    #[test]
+    fn test_draw_pile_diff() {
+        let deck = Deck::from(vec![1_u8, 2, 2, 3]);
+        let (mine, theirs) = deck.draw_pile_diff(&deck.clone());
+        assert!(mine.is_empty());
+        assert!(theirs.is_empty());
+
+        let (mine, theirs) = deck.draw_pile_diff(&Deck::from(vec![3, 2, 1, 2]));
+        assert!(mine.is_empty());
+        assert!(theirs.is_empty());
+
+        let other = Deck::from(vec![2, 4, 3, 4]);
+        assert_eq!(deck.draw_pile_diff(&other), (vec![1, 2], vec![4, 4]));
+        assert_eq!(other.draw_pile_diff(&deck), (vec![4, 4], vec![1, 2]));
+
+        assert_eq!(
+            deck.draw_pile_diff(&Deck::new()),
+            (vec![1, 2, 2, 3], vec![])
+        );
+    }
+
+    #[test]
+    fn test_peek_bottom_n_from_draw() {
+        let mut deck = Deck::<u8>::new();
+        assert!(deck.peek_bottom_n_from_draw(2).is_empty());
+
+        (0..5).for_each(|i| deck.put_top(i));
+        assert!(deck.peek_bottom_n_from_draw(0).is_empty());
+        assert_eq!(deck.peek_bottom_n_from_draw(2), [0, 1]);
+        assert_eq!(deck.peek_bottom_n_from_draw(9), [0, 1, 2, 3, 4]);
+        assert_eq!(deck.remaining(), 5);
+    }
+
+    #[test]
+    fn test_as_mut_slice() {
+        let mut deck = Deck::<u8>::new();
+        assert!(deck.draw_pile_as_mut_slice().is_empty());
+
+        [3, 1, 2].into_iter().for_each(|i| deck.put_top(i));
+        [6, 5, 4].into_iter().for_each(|i| deck.discard(i));
+        [7, 8].into_iter().for_each(|i| deck.remove(i));
+
+        deck.draw_pile_as_mut_slice().sort_by_cached_key(|x| *x);
+        assert_eq!(deck.peek_draw_pile(), [1, 2, 3]);
+
+        deck.discard_pile_as_mut_slice().reverse();
+        assert_eq!(deck.peek_discard_pile(), [4, 5, 6]);
+
+        deck.removed_pile_as_mut_slice()[0] = 9;
+        assert_eq!(deck.peek_removed_pile(), [9, 8]);
+    }
+
+    #[test]
+    fn test_count_occurrences_total() {
+        let mut deck = Deck::<u8>::new();
+        (0..4).for_each(|_| deck.put_top(1));
+        deck.mill(1);
+        let x = deck.draw_top().unwrap();
+        deck.remove(x);
+
+        assert_eq!(deck.count_occurrences(&1), 2);
+        assert_eq!(deck.count_occurrences_in_discard(&1), 1);
+        assert_eq!(deck.count_occurrences_in_removed(&1), 1);
+        assert_eq!(deck.count_occurrences_total(&1), 4);
+        assert_eq!(deck.count_occurrences_total(&0), 0);
+    }
+
+    #[test]
+    fn test_replace_draw_pile() {
+        let mut deck = Deck::from(vec![0_u8, 1, 2]);
+        deck.discard(3);
+        deck.remove(4);
+
+        assert_eq!(deck.replace_draw_pile(vec![5, 6]), [0, 1, 2]);
+        assert_eq!(deck.peek_draw_pile(), [5, 6]);
+        assert_eq!(deck.peek_discard_pile(), [3]);
+        assert_eq!(deck.peek_removed_pile(), [4]);
+
+        assert_eq!(deck.replace_removed_pile(vec![7]), [4]);
+        assert_eq!(deck.peek_removed_pile(), [7]);
+
+        assert_eq!(deck.replace_draw_pile(Vec::new()), [5, 6]);
+        assert!(!deck.can_draw());
+    }
+
+    #[test]
+    fn test_replace_discard_pile() {
+        let mut deck = Deck::from(vec![0_u8]);
+        (1..4).for_each(|i| deck.discard(i));
+
+        let new_pile = vec![8, 9];
+        let len = new_pile.len();
+        assert_eq!(deck.replace_discard_pile(new_pile), [1, 2, 3]);
+        assert_eq!(deck.discard_count(), len);
+        assert_eq!(deck.peek_discard_pile(), [8, 9]);
+        assert_eq!(deck.peek_draw_pile(), [0]);
+
+        let mut restored = Deck::new();
+        restored.replace_draw_pile(deck.peek_draw_pile().to_vec());
+        restored.replace_discard_pile(deck.peek_discard_pile().to_vec());
+        restored.replace_removed_pile(deck.peek_removed_pile().to_vec());
+        assert_eq!(restored, deck);
+    }
+
+    #[test]
+    fn test_draw_top_or_else() {
+        let mut deck = Deck::from(vec![1_u8, 2]);
+        let mut calls = 0;
+
+        assert_eq!(
+            deck.draw_top_or_else(|| {
+                calls += 1;
+                0
+            }),
+            2
+        );
+        assert_eq!(deck.draw_top_or_else(|| 0), 1);
+        assert_eq!(calls, 0);
+
+        assert_eq!(
+            deck.draw_top_or_else(|| {
+                calls += 1;
+                9
+            }),
+            9
+        );
+        assert_eq!(calls, 1);
+        assert!(!deck.can_draw());
+        assert!(deck.peek_discard_pile().is_empty());
+    }
+
+    #[test]
+    fn test_draw_matching_and_discard_others() {
+        let mut deck = Deck::from(vec![1_u8, 2, 3, 4, 5, 6, 7]);
+        assert!(deck
+            .draw_matching_and_discard_others(0, |_| true)
+            .is_empty());
+        assert_eq!(deck.remaining(), 7);
+
+        assert_eq!(
+            deck.draw_matching_and_discard_others(2, |x| x % 2 == 0),
+            [6, 4]
+        );
+        assert_eq!(deck.peek_draw_pile(), [1, 2, 3]);
+        assert_eq!(deck.peek_discard_pile(), [7, 5]);
+
+        assert_eq!(deck.draw_matching_and_discard_others(5, |x| *x == 2), [2]);
+        assert!(!deck.can_draw());
+        assert_eq!(deck.peek_discard_pile(), [7, 5, 3, 1]);
+    }
+
+    #[test]
+    fn test_shuffle_into_draw() {
+        let mut deck = Deck::<u8>::new();
+        deck.shuffle_into_draw(vec![1, 2], &mut thread_rng());
+        assert_eq!(deck.remaining(), 2);
+
+        let build = |seed| {
+            let mut deck = Deck::from((0..40).collect::<Vec<u8>>());
+            deck.shuffle_into_draw((40..50).collect(), &mut ChaCha8Rng::seed_from_u64(seed));
+            deck
+        };
+        assert_eq!(build(8), build(8));
+        assert_ne!(build(8), build(9));
+
+        let mut deck = build(8);
+        assert_eq!(deck.remaining(), 50);
+        deck.sort_all_piles();
+        assert_eq!(deck.peek_draw_pile(), (0..50).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn test_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<Deck<i32>>();
+        assert_send_sync::<DeckTransaction<i32>>();
+    }
+
+    #[test]
+    fn test_draw_until_n_found() {
+        let mut deck = Deck::from(vec![1_u8, 2, 3, 4, 5, 6, 7]);
+        deck.discard(10);
+        assert_eq!(deck.draw_until_n_found(|_| true, 0), (vec![], vec![]));
+
+        assert_eq!(
+            deck.draw_until_n_found(|x| x % 2 == 0, 2),
+            (vec![6, 4], vec![7, 5])
+        );
+        assert_eq!(deck.peek_draw_pile(), [1, 2, 3]);
+        assert_eq!(deck.peek_discard_pile(), [10]);
+
+        assert_eq!(
+            deck.draw_until_n_found(|x| *x > 5, 2),
+            (vec![], vec![3, 2, 1])
+        );
+        assert!(!deck.can_draw());
+    }
+
+    #[test]
+    fn test_zip_piles() {
+        let mut deck = Deck::<u8>::new();
+        assert_eq!(deck.zip_piles().count(), 0);
+
+        (0..3).for_each(|i| deck.put_top(i));
+        (10..12).for_each(|i| deck.discard(i));
+        deck.remove(20);
+
+        let zipped: Vec<_> = deck.zip_piles().collect();
+        assert_eq!(
+            zipped,
+            [
+                (&0, Some(&10), Some(&20)),
+                (&1, Some(&11), None),
+                (&2, None, None),
+            ]
+        );
+        assert_eq!(deck.zip_piles().size_hint(), (3, Some(3)));
+        assert_eq!(deck.zip_piles().len(), 3);
+
+        // The draw pile sets the length, so longer discard cards are not yielded.
+        let mut late = Deck::from(vec![0_u8]);
+        (10..14).for_each(|i| late.discard(i));
+        late.remove(20);
+        late.remove(21);
+        assert!(late.discard_count() > late.remaining());
+        let zipped: Vec<_> = late.zip_piles().collect();
+        assert_eq!(zipped, [(&0, Some(&10), Some(&20))]);
+        assert_eq!(late.zip_piles().len(), 1);
+    }
+
+    #[test]
+    fn test_hypergeometric_draw_probability() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+        let deck = Deck::from((0..60).collect::<Vec<u8>>());
+        assert_eq!(deck.hypergeometric_draw_probability(0, 7), 0.0);
+        assert_eq!(deck.hypergeometric_draw_probability(4, 0), 0.0);
+        assert_eq!(deck.hypergeometric_draw_probability(60, 1), 1.0);
+        assert_eq!(deck.hypergeometric_draw_probability(4, 57), 1.0);
+        assert_eq!(deck.hypergeometric_draw_probability(4, 100), 1.0);
+
+        assert!(close(
+            deck.hypergeometric_draw_probability(1, 1),
+            1.0 / 60.0
+        ));
+        assert!(close(deck.hypergeometric_draw_probability(30, 1), 0.5));
+        // 1 - C(56, 7) / C(60, 7)
+        assert!(close(
+            deck.hypergeometric_draw_probability(4, 7),
+            1.0 - 231917400.0 / 386206920.0
+        ));
+
+        let large = Deck::from(vec![0_u8; 100_000]);
+        let p = large.hypergeometric_draw_probability(10, 5000);
+        assert!(p.is_finite() && p > 0.4 && p < 0.41);
+    }
+
+    #[test]
+    #[should_panic(expected = "successes cannot fit")]
+    fn test_hypergeometric_draw_probability_too_many_successes() {
+        let deck = Deck::from(vec![0_u8; 3]);
+        deck.hypergeometric_draw_probability(4, 1);
+    }
+
+    #[test]
+    fn test_sort_draw_then_shuffle_groups() {
+        let mut deck = Deck::<u8>::new();
+        deck.sort_draw_then_shuffle_groups(|x| *x);
+        assert!(!deck.can_draw());
+
+        let ordered: Vec<u8> = (0..30).collect();
+        let mut seen_orders = HashSet::new();
+        for _ in 0..100 {
+            let mut deck = Deck::from(ordered.clone());
+            deck.sort_draw_then_shuffle_groups(|x| x % 3);
+
+            let pile = deck.peek_draw_pile();
+            for (i, group) in pile.chunks(10).enumerate() {
+                assert!(group.iter().all(|x| usize::from(x % 3) == i));
+            }
+            seen_orders.insert(pile[..10].to_vec());
+        }
+        assert!(seen_orders.len() > 1);
+    }
+
+    #[test]
+    fn test_cut_random() {
+        let mut deck = Deck::<u8>::new();
+        deck.cut_random(&mut thread_rng());
+        assert!(!deck.can_draw());
+
+        let ordered = Deck::from((0..5).collect::<Vec<u8>>());
+        let mut first = ordered.clone();
+        let mut second = ordered.clone();
+        first.cut_random(&mut ChaCha8Rng::seed_from_u64(12));
+        second.cut_random(&mut ChaCha8Rng::seed_from_u64(12));
+        assert_eq!(first, second);
+
+        let mut rng = ChaCha8Rng::seed_from_u64(13);
+        let mut bottoms = HashSet::new();
+        for _ in 0..1000 {
+            let mut deck = ordered.clone();
+            deck.cut_random(&mut rng);
+            assert!(is_subsequence(
+                &[0, 1, 2, 3, 4],
+                &[deck.peek_draw_pile(), deck.peek_draw_pile()].concat()
+            ));
+            bottoms.insert(deck[0]);
+        }
+        assert_eq!(bottoms.len(), 5);
+    }
+
+    #[test]
+    fn test_map_draw() {
+        let mut deck = Deck::<u8>::new();
+        (0..3).for_each(|i| deck.put_top(i));
+        (3..5).for_each(|i| deck.discard(i));
+        deck.remove(5);
+
+        let mapped: Deck<String> = deck.map_draw(|x| format!("card {}", x));
+        assert_eq!(mapped.peek_draw_pile(), ["card 0", "card 1", "card 2"]);
+        assert_eq!(mapped.peek_discard_pile(), ["card 3", "card 4"]);
+        assert_eq!(mapped.peek_removed_pile(), ["card 5"]);
+
+        let mut calls = 0;
+        let lengths = mapped.map_draw(|x| {
+            calls += 1;
+            x.len()
+        });
+        assert_eq!(calls, 6);
+        assert_eq!(lengths.remaining(), 3);
+        assert_eq!(lengths.discard_count(), 2);
+        assert_eq!(lengths.removed_count(), 1);
+    }
+
+    #[test]
+    fn test_drain_draw_top_n() {
+        let mut deck = Deck::<u8>::new();
+        assert_eq!(deck.drain_draw_top_n(3).count(), 0);
+
+        (0..6).for_each(|i| deck.put_top(i));
+        assert_eq!(deck.drain_draw_top_n(0).count(), 0);
+        assert_eq!(deck.remaining(), 6);
+
+        let hand: Vec<u8> = deck.drain_draw_top_n(2).collect();
+        assert_eq!(hand, [5, 4]);
+        assert_eq!(deck.remaining(), 4);
+
+        assert_eq!(deck.drain_draw_top_n(3).next(), Some(3));
+        assert_eq!(deck.peek_draw_pile(), [0]);
+
+        assert_eq!(deck.drain_draw_top_n(10).collect::<Vec<_>>(), [0]);
+        assert!(!deck.can_draw());
+    }
+
+    #[test]
+    fn test_filter_map_draw() {
+        let mut deck = Deck::<u8>::new();
+        assert!(deck.filter_map_draw(Some).is_empty());
+
+        (0..6).for_each(|i| deck.put_top(i));
+        deck.discard(10);
+
+        let valid: Vec<String> =
+            deck.filter_map_draw(|x| (x % 2 == 0).then(|| format!("card {}", x)));
+        assert_eq!(valid, ["card 0", "card 2", "card 4"]);
+        assert!(!deck.can_draw());
+        assert_eq!(deck.peek_discard_pile(), [10]);
+    }
+
+    #[test]
+    fn test_stats() {
+        let empty = Deck::<u8>::new().stats();
+        assert_eq!(empty.total, 0);
+        assert_eq!(empty.draw_fraction, 0.0);
+
+        let mut deck = Deck::from(vec![1_u8, 1, 2, 3, 3, 3]);
+        [4, 4].into_iter().for_each(|i| deck.discard(i));
+        [5, 6].into_iter().for_each(|i| deck.remove(i));
+
+        let stats = deck.stats();
+        assert_eq!(stats.draw_count, deck.remaining());
+        assert_eq!(stats.discard_count, deck.discard_count());
+        assert_eq!(stats.removed_count, deck.removed_count());
+        assert_eq!(stats.total, deck.total_count());
+        assert_eq!(stats.draw_fraction, deck.remaining_fraction());
+        assert_eq!(stats.draw_fraction, 0.6);
+
+        let with_unique = deck.stats_with_unique();
+        assert_eq!(with_unique.stats, stats);
+        assert_eq!(with_unique.unique_in_draw, deck.unique_count());
+        assert_eq!(with_unique.unique_in_draw, 3);
+        assert_eq!(with_unique.unique_in_discard, deck.unique_count_discard());
+        assert_eq!(with_unique.unique_in_discard, 1);
+    }
+
+    #[test]
+    fn test_peek_all_piles() {
+        let mut deck = Deck::from(vec![0_u8, 1]);
+        deck.discard(2);
+        deck.remove(3);
+        deck.remove(4);
+
+        let shared = &deck;
+        let (draw, discard, removed) = shared.peek_all_piles();
+        assert_eq!(draw, shared.peek_draw_pile());
+        assert_eq!(discard, shared.peek_discard_pile());
+        assert_eq!(removed, shared.peek_removed_pile());
+        assert_eq!(removed, [3, 4]);
+    }
+
+    #[test]
+    fn test_move_n_from_discard_to_draw() {
+        let mut deck = Deck::from(vec![0_u8, 1]);
+        (2..6).for_each(|i| deck.discard(i));
+
+        assert_eq!(deck.move_n_from_discard_to_draw(0), 0);
+        assert_eq!(deck.peek_draw_pile(), [0, 1]);
+        assert_eq!(deck.discard_count(), 4);
+
+        assert_eq!(deck.move_n_from_discard_to_draw(2), 2);
+        assert_eq!(deck.peek_draw_pile(), [4, 5, 0, 1]);
+        assert_eq!(deck.peek_discard_pile(), [2, 3]);
+
+        assert_eq!(deck.move_n_from_discard_to_draw(10), 2);
+        assert_eq!(deck.peek_draw_pile(), [2, 3, 4, 5, 0, 1]);
+        assert_eq!(deck.discard_count(), 0);
+    }
+
+    #[test]
+    fn test_clear_all_piles() {
+        let mut deck = Deck::from(vec![0_u8, 1, 2]);
+        (3..5).for_each(|i| deck.discard(i));
+        deck.remove(5);
+
+        let sizes = deck.pile_sizes();
+        assert_eq!(sizes, (3, 2, 1));
+        assert_eq!(deck.clear_all_piles(), sizes);
+        assert_eq!(deck.pile_sizes(), (0, 0, 0));
+        assert_eq!(deck.clear_all_piles(), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_top_n_matching() {
+        let deck = Deck::from((0..10).collect::<Vec<u8>>());
+
+        assert_eq!(deck.top_n_matching(3, |x| x % 2 == 0), [&8, &6, &4]);
+        assert_eq!(deck.top_n_matching(3, |x| x % 4 == 0), [&8, &4, &0]);
+        assert_eq!(deck.top_n_matching(5, |x| *x < 2), [&1, &0]);
+        assert!(deck.top_n_matching(3, |x| *x > 10).is_empty());
+        assert!(deck.top_n_matching(0, |_| true).is_empty());
+        assert_eq!(deck.remaining(), 10);
+    }
+
+    #[test]
+    fn test_put_discard_top_bottom() {
+        let mut deck = Deck::<u8>::new();
+        deck.put_discard_bottom(1);
+        deck.put_discard_top(2);
+        deck.put_discard_bottom(3);
+        deck.discard(4);
+        deck.put_discard_top(5);
+        deck.put_discard_bottom(6);
+
+        assert_eq!(deck.peek_discard_pile(), [6, 3, 1, 2, 4, 5]);
+        assert_eq!(deck.pop_discard(), Some(5));
+        assert!(!deck.can_draw());
+    }
+
+    #[test]
+    fn test_insert_at_position_from_bottom() {
+        let mut deck = Deck::<u8>::new();
+        deck.insert_at_position_from_bottom(0, 3);
+        assert_eq!(deck.peek_draw_pile(), [0]);
+
+        let mut deck = Deck::from(vec![0_u8, 1, 2]);
+        deck.insert_at_position_from_bottom(10, 0);
+        assert_eq!(deck.peek_draw_pile(), [10, 0, 1, 2]);
+
+        deck.insert_at_position_from_bottom(11, 2);
+        assert_eq!(deck.peek_draw_pile(), [10, 0, 11, 1, 2]);
+
+        deck.insert_at_position_from_bottom(12, 5);
+        assert_eq!(deck.peek_draw_pile(), [10, 0, 11, 1, 2, 12]);
+
+        deck.insert_at_position_from_bottom(13, 100);
+        assert_eq!(deck.draw_top(), Some(13));
+    }
+
+    #[test]
+    fn test_draw_pile_same_multiset() {
+        let deck = Deck::from(vec![5_u8, 1, 4, 1]);
+        let mut shuffled = deck.clone();
+        shuffled.riffle_shuffle();
+        assert!(deck.draw_pile_same_multiset(&shuffled));
+
+        let mut missing = deck.clone();
+        missing.draw_top();
+        assert!(!deck.draw_pile_same_multiset(&missing));
+        assert!(!missing.draw_pile_same_multiset(&deck));
+
+        let mut different = deck.clone();
+        different[0] = 9;
+        assert!(!deck.draw_pile_same_multiset(&different));
+    }
+
+    #[test]
+    fn test_sum() {
+        let mut deck: Deck<u8> = (0..5).sum();
+        assert_eq!(deck.pile_sizes(), (5, 0, 0));
+        assert_eq!(deck.peek_draw_pile(), [0, 1, 2, 3, 4]);
+        assert_eq!(deck.draw_top(), Some(4));
+
+        let empty = std::iter::empty::<u8>().sum::<Deck<u8>>();
+        assert_eq!(empty, Deck::default());
+    }
+
+    #[test]
+    fn test_move_draw_to_discard() {
+        let mut deck = Deck::<u8>::new();
+        assert!(!deck.move_draw_top_to_discard_bottom());
+        assert!(!deck.move_draw_bottom_to_discard_top());
+
+        let mut deck = Deck::from(vec![0_u8, 1, 2, 3]);
+        deck.discard(10);
+        assert!(deck.move_draw_top_to_discard_bottom());
+        assert!(deck.move_draw_bottom_to_discard_top());
+        assert_eq!(deck.peek_draw_pile(), [1, 2]);
+        assert_eq!(deck.peek_discard_pile(), [3, 10, 0]);
+    }
+
+    #[test]
+    fn test_move_draw_to_removed() {
+        let mut deck = Deck::<u8>::new();
+        assert!(!deck.move_draw_top_to_removed_bottom());
+        assert!(!deck.move_draw_bottom_to_removed_top());
+
+        let mut deck = Deck::from(vec![0_u8, 1, 2, 3]);
+        deck.remove(10);
+        assert!(deck.move_draw_top_to_removed_bottom());
+        assert!(deck.move_draw_bottom_to_removed_top());
+        assert_eq!(deck.peek_draw_pile(), [1, 2]);
+        assert_eq!(deck.peek_removed_pile(), [3, 10, 0]);
+        assert!(deck.peek_discard_pile().is_empty());
+    }
+
+    #[test]
+    fn test_drain_discard_sorted() {
+        let mut deck = Deck::from(vec![0_u8]);
+        assert!(deck.drain_discard_sorted().is_empty());
+
+        [5, 2, 9, 2, 7].into_iter().for_each(|i| deck.discard(i));
+        let count = deck.discard_count();
+        let drained = deck.drain_discard_sorted();
+        assert_eq!(drained.len(), count);
+        assert_eq!(drained, [2, 2, 5, 7, 9]);
+        assert_eq!(deck.discard_count(), 0);
+        assert_eq!(deck.peek_draw_pile(), [0]);
+
+        [5, 2, 9].into_iter().for_each(|i| deck.discard(i));
+        assert_eq!(deck.drain_discard_sorted_by(|a, b| b.cmp(a)), [9, 5, 2]);
+        assert!(deck.peek_discard_pile().is_empty());
+    }
+
+    #[test]
+    fn test_bulk_move_to_discard() {
+        let mut deck = Deck::from((0..8).collect::<Vec<u8>>());
+        deck.discard(10);
+
+        deck.bulk_move_to_discard(&[]);
+        assert_eq!(deck.pile_sizes(), (8, 1, 0));
+
+        deck.bulk_move_to_discard(&[0, 3, 4, 7]);
+        assert_eq!(deck.peek_draw_pile(), [1, 2, 5, 6]);
+        assert_eq!(deck.peek_discard_pile(), [10, 0, 3, 4, 7]);
+        assert_eq!(deck.total_count(), 9);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_bulk_move_to_discard_out_of_bounds() {
+        let mut deck = Deck::from(vec![0_u8, 1, 2]);
+        deck.bulk_move_to_discard(&[1, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "strictly ascending")]
+    fn test_bulk_move_to_discard_unsorted() {
+        let mut deck = Deck::from(vec![0_u8, 1, 2]);
+        deck.bulk_move_to_discard(&[2, 1]);
+    }
+
+    #[test]
+    fn test_sample_draw_indices() {
+        let mut deck = Deck::<u8>::new();
+        assert!(deck.sample_draw_indices(3).is_empty());
+
+        (0..10).for_each(|i| deck.put_top(i));
+        for n in 0..15 {
+            let indices = deck.sample_draw_indices(n);
+            assert_eq!(indices.len(), n.min(10));
+            assert!(indices.windows(2).all(|w| w[0] < w[1]));
+            assert!(indices.iter().all(|i| deck.peek_at(*i).is_some()));
+        }
+        assert_eq!(deck.sample_draw_indices(20), (0..10).collect::<Vec<_>>());
+        assert_eq!(deck.remaining(), 10);
+    }
+
+    #[test]
+    fn test_put_sparse_batched() {
+        let mut deck = Deck::<usize>::new();
+        deck.put_sparse_batched(Vec::new());
+        assert!(!deck.can_draw());
+
+        (0..20).for_each(|i| deck.put_top(i));
+        deck.put_sparse_batched(vec![vec![100, 101], vec![], vec![200, 201, 202]]);
+        assert_eq!(deck.remaining(), 25);
+
+        let originals: Vec<_> = deck.peek_draw_pile().iter().filter(|x| **x < 100).collect();
+        assert!(originals.iter().enumerate().all(|(i, x)| **x == i));
+        for x in [100, 101, 200, 201, 202] {
+            assert_eq!(deck.count_occurrences(&x), 1);
+        }
+    }
+
+    #[test]
+    fn test_draw_iter_top_first() {
+        let mut deck = Deck::<u8>::new();
+        assert_eq!(deck.peek_top(), None);
+        assert_eq!(deck.draw_iter_top_first().next(), None);
+
+        (0..4).for_each(|i| deck.put_top(i));
+        assert_eq!(deck.peek_top(), Some(&3));
+        assert_eq!(deck.draw_iter_top_first().next(), deck.peek_top());
+        assert_eq!(
+            deck.draw_iter_top_first().collect::<Vec<_>>(),
+            [&3, &2, &1, &0]
+        );
+        assert_eq!(
+            deck.draw_iter_top_first().rev().collect::<Vec<_>>(),
+            [&0, &1, &2, &3]
+        );
+    }
+
+    #[test]
+    fn test_dedup_across_piles() {
+        let mut deck = Deck::from(vec![0_u8, 1, 2]);
+        deck.discard(3);
+        deck.remove(4);
+        assert!(deck.dedup_across_piles().is_empty());
+        assert_eq!(deck.pile_sizes(), (3, 1, 1));
+
+        let mut deck = Deck::from(vec![0_u8, 1, 0, 2]);
+        assert_eq!(deck.dedup_across_piles(), [0]);
+        assert_eq!(deck.peek_draw_pile(), [0, 1, 2]);
+
+        let mut deck = Deck::from(vec![0_u8, 1]);
+        [1, 2, 3, 2].into_iter().for_each(|i| deck.discard(i));
+        [0, 3, 4].into_iter().for_each(|i| deck.remove(i));
+        assert_eq!(deck.dedup_across_piles(), [1, 2, 0, 3]);
+        assert_eq!(deck.peek_draw_pile(), [0, 1]);
+        assert_eq!(deck.peek_discard_pile(), [2, 3]);
+        assert_eq!(deck.peek_removed_pile(), [4]);
+        assert!(deck.validate_no_duplicates());
+    }
+
+    #[test]
+    fn test_peek_top_and_discard_if() {
+        let mut deck = Deck::<u8>::new();
+        assert!(!deck.peek_top_and_discard_if(|_| true));
+
+        let mut deck = Deck::from(vec![1_u8, 2]);
+        assert!(!deck.peek_top_and_discard_if(|x| *x == 1));
+        assert_eq!(deck.pile_sizes(), (2, 0, 0));
+
+        assert!(deck.peek_top_and_discard_if(|x| *x == 2));
+        assert_eq!(deck.peek_draw_pile(), [1]);
+        assert_eq!(deck.peek_discard_pile(), [2]);
+    }
+
+    #[test]
+    fn test_make_contiguous() {
+        let mut deck = Deck::from(vec![0_u8, 1, 2]);
+        deck.put_bottom(3);
+        deck.make_contiguous();
+        assert_eq!(deck.as_contiguous_draw_slice(), deck.peek_draw_pile());
+        assert_eq!(deck.as_contiguous_draw_slice(), [3, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_put_sparse_exact() {
+        let mut deck = Deck::<u8>::new();
+        deck.put_sparse_exact(Vec::new(), Vec::new());
+        deck.put_sparse_exact(vec![10, 11], vec![0, 1]);
+        assert_eq!(deck.peek_draw_pile(), [10, 11]);
+
+        let mut deck = Deck::from(vec![0_u8, 1, 2, 3]);
+        deck.put_sparse_exact(vec![10, 11, 12], vec![0, 3, 6]);
+        assert_eq!(deck.peek_draw_pile(), [10, 0, 1, 11, 2, 3, 12]);
+
+        deck.put_sparse_exact(vec![13], vec![7]);
+        assert_eq!(deck.draw_top(), Some(13));
+    }
+
+    #[test]
+    #[should_panic(expected = "exactly one position per element")]
+    fn test_put_sparse_exact_length_mismatch() {
+        let mut deck = Deck::from(vec![0_u8, 1]);
+        deck.put_sparse_exact(vec![10, 11], vec![0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "strictly increasing")]
+    fn test_put_sparse_exact_unsorted() {
+        let mut deck = Deck::from(vec![0_u8, 1]);
+        deck.put_sparse_exact(vec![10, 11], vec![1, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_put_sparse_exact_out_of_range() {
+        let mut deck = Deck::from(vec![0_u8, 1]);
+        deck.put_sparse_exact(vec![10, 11], vec![1, 4]);
+    }
+
+    #[test]
+    fn test_peek_piles() {
+        let mut deck = Deck::from(vec![2_u8, 1]);
+        deck.discard(3);
+        deck.remove(4);
+
+        deck.peek_draw_pile_mut().sort();
+        deck.peek_discard_pile_mut()[0] = 5;
+        deck.peek_removed_pile_mut()[0] += 1;
+        assert_eq!(deck.peek_draw_pile(), [1, 2]);
+        assert_eq!(deck.peek_discard_pile(), [5]);
+        assert_eq!(deck.peek_removed_pile(), [5]);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_see_piles() {
+        let mut deck = Deck::from(vec![0_u8, 1]);
+        deck.discard(2);
+        deck.remove(3);
+
+        assert_eq!(deck.see_draw(), deck.peek_draw_pile());
+        assert_eq!(deck.see_discarded(), deck.peek_discard_pile());
+        assert_eq!(deck.see_removed(), deck.peek_removed_pile());
+        assert_eq!(deck.see_all_piles(), deck.peek_all_piles());
+    }
+
+    #[test]
+    fn test_new_with_draw() {
+        let deck = Deck::new_with_draw(vec![3_u8, 1, 2]);
+        assert_eq!(deck.peek_draw_pile(), [3, 1, 2]);
+        assert_eq!(deck.peek_top(), Some(&2));
+        assert!(deck.peek_discard_pile().is_empty());
+        assert!(deck.peek_removed_pile().is_empty());
+        assert_eq!(deck, Deck::from(vec![3, 1, 2]));
+    }
+
+    #[test]
+    fn test_move_cards_from_discard_to_draw_by_predicate() {
+        let mut deck = Deck::from(vec![10_u8, 11]);
+        assert_eq!(
+            deck.move_cards_from_discard_to_draw_by_predicate(|_| true),
+            0
+        );
+
+        (0..6).for_each(|i| deck.discard(i));
+        assert_eq!(
+            deck.move_cards_from_discard_to_draw_by_predicate(|x| *x > 9),
+            0
+        );
+        assert_eq!(deck.pile_sizes(), (2, 6, 0));
+
+        assert_eq!(
+            deck.move_cards_from_discard_to_draw_by_predicate(|x| x % 2 == 1),
+            3
+        );
+        assert_eq!(deck.peek_draw_pile(), [1, 3, 5, 10, 11]);
+        assert_eq!(deck.peek_discard_pile(), [0, 2, 4]);
+    }
+
+    #[test]
+    fn test_as_ref_as_mut() {
+        let mut deck = Deck::from(vec![1_u8, 3, 5, 7]);
+        deck.discard(9);
+
+        let slice: &[u8] = deck.as_ref();
+        assert_eq!(slice, deck.peek_draw_pile());
+        assert_eq!(deck.as_ref().binary_search(&5), Ok(2));
+
+        let slice: &mut [u8] = deck.as_mut();
+        slice.reverse();
+        assert_eq!(deck.draw_top(), Some(1));
+        assert_eq!(deck.draw_bottom(), Some(7));
     }
 }
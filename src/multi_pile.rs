@@ -0,0 +1,142 @@
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MultiPileDeck<T, const N: usize> {
+    piles: [Vec<T>; N],
+}
+
+impl<T, const N: usize> MultiPileDeck<T, N> {
+    pub fn new() -> Self {
+        Self {
+            piles: std::array::from_fn(|_| Vec::new()),
+        }
+    }
+
+    pub fn draw_from(&mut self, pile: usize) -> Option<T> {
+        self.pile_mut(pile).pop()
+    }
+
+    pub fn put_to(&mut self, pile: usize, item: T) {
+        self.pile_mut(pile).push(item);
+    }
+
+    pub fn shuffle_pile(&mut self, pile: usize) {
+        self.pile_mut(pile).shuffle(&mut thread_rng());
+    }
+
+    pub fn transfer(&mut self, from: usize, to: usize) -> bool {
+        Self::check_pile(to);
+        match self.draw_from(from) {
+            Some(x) => {
+                self.put_to(to, x);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn peek_pile(&self, pile: usize) -> &[T] {
+        Self::check_pile(pile);
+        self.piles[pile].as_slice()
+    }
+
+    #[deprecated(note = "use `peek_pile` instead")]
+    pub fn see_pile(&self, pile: usize) -> &[T] {
+        self.peek_pile(pile)
+    }
+
+    fn pile_mut(&mut self, pile: usize) -> &mut Vec<T> {
+        Self::check_pile(pile);
+        &mut self.piles[pile]
+    }
+
+    fn check_pile(pile: usize) {
+        assert!(
+            pile < N,
+            "pile index {} is out of range for a deck with {} piles",
+            pile,
+            N
+        );
+    }
+}
+
+impl<T, const N: usize> Default for MultiPileDeck<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_piles() {
+        let mut deck = MultiPileDeck::<u8, 2>::new();
+        assert_eq!(deck.draw_from(0), None);
+        assert!(!deck.transfer(0, 1));
+
+        (0..3).for_each(|i| deck.put_to(0, i));
+        assert!(deck.transfer(0, 1));
+        assert!(deck.transfer(0, 1));
+        assert_eq!(deck.peek_pile(0), [0]);
+        assert_eq!(deck.peek_pile(1), [2, 1]);
+
+        assert_eq!(deck.draw_from(1), Some(1));
+        assert_eq!(deck.draw_from(0), Some(0));
+        assert_eq!(deck.draw_from(0), None);
+    }
+
+    #[test]
+    fn test_four_piles() {
+        let mut deck = MultiPileDeck::<u8, 4>::default();
+        (0..20).for_each(|i| deck.put_to(3, i));
+        deck.shuffle_pile(3);
+
+        let mut pile = deck.peek_pile(3).to_vec();
+        pile.sort();
+        assert_eq!(pile, (0..20).collect::<Vec<_>>());
+
+        for to in 0..3 {
+            assert!(deck.transfer(3, to));
+        }
+        assert_eq!(deck.peek_pile(3).len(), 17);
+        assert!((0..3).all(|i| deck.peek_pile(i).len() == 1));
+    }
+
+    #[test]
+    fn test_eight_piles() {
+        let mut deck = MultiPileDeck::<u8, 8>::new();
+        deck.put_to(0, 42);
+        for from in 0..7 {
+            assert!(deck.transfer(from, from + 1));
+            assert!(deck.peek_pile(from).is_empty());
+        }
+        assert_eq!(deck.peek_pile(7), [42]);
+        assert!(!deck.transfer(6, 7));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_see_pile() {
+        let mut deck = MultiPileDeck::<u8, 2>::new();
+        deck.put_to(1, 5);
+        assert_eq!(deck.see_pile(1), deck.peek_pile(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "pile index 2 is out of range for a deck with 2 piles")]
+    fn test_pile_out_of_range() {
+        let mut deck = MultiPileDeck::<u8, 2>::new();
+        deck.put_to(2, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "pile index 4 is out of range")]
+    fn test_transfer_out_of_range() {
+        let mut deck = MultiPileDeck::<u8, 4>::new();
+        deck.put_to(0, 0);
+        deck.transfer(0, 4);
+    }
+}
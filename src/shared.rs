@@ -0,0 +1,200 @@
+#[cfg(feature = "async")]
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+#[cfg(feature = "async")]
+use std::task::{Context, Poll, Waker};
+
+#[cfg(feature = "async")]
+use futures::Stream;
+
+use crate::Deck;
+
+pub struct SharedDeck<T> {
+    inner: Arc<Mutex<Shared<T>>>,
+}
+
+struct Shared<T> {
+    deck: Deck<T>,
+    #[cfg(feature = "async")]
+    wakers: Vec<Waker>,
+    #[cfg(feature = "async")]
+    closed: bool,
+}
+
+impl<T> SharedDeck<T> {
+    pub fn new(deck: Deck<T>) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Shared {
+                deck,
+                #[cfg(feature = "async")]
+                wakers: Vec::new(),
+                #[cfg(feature = "async")]
+                closed: false,
+            })),
+        }
+    }
+
+    pub fn draw_top(&self) -> Option<T> {
+        self.lock().deck.draw_top()
+    }
+
+    pub fn put_top(&self, x: T) {
+        let mut shared = self.lock();
+        shared.deck.put_top(x);
+        #[cfg(feature = "async")]
+        shared.wake_all();
+    }
+
+    pub fn shuffle_draw(&self) {
+        self.lock().deck.shuffle_draw();
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.lock().deck.remaining()
+    }
+
+    #[cfg(feature = "async")]
+    pub fn close(&self) {
+        let mut shared = self.lock();
+        shared.closed = true;
+        shared.wake_all();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Shared<T>> {
+        self.inner.lock().expect("shared deck lock poisoned")
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> Shared<T> {
+    fn wake_all(&mut self) {
+        self.wakers.drain(..).for_each(Waker::wake);
+    }
+}
+
+impl<T> Clone for SharedDeck<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> Stream for SharedDeck<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut shared = self.lock();
+        if let Some(x) = shared.deck.draw_top() {
+            return Poll::Ready(Some(x));
+        }
+        if shared.closed {
+            return Poll::Ready(None);
+        }
+
+        if !shared.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+            shared.wakers.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::thread;
+    #[cfg(feature = "async")]
+    use std::time::Duration;
+
+    #[cfg(feature = "async")]
+    use futures::executor::block_on;
+    #[cfg(feature = "async")]
+    use futures::{poll, StreamExt};
+
+    use super::*;
+
+    #[test]
+    fn test_shared_deck() {
+        let deck = SharedDeck::new(Deck::from(vec![1_u8, 2]));
+        let other = deck.clone();
+        assert_eq!(deck.remaining(), 2);
+
+        other.put_top(3);
+        assert_eq!(deck.remaining(), 3);
+        deck.shuffle_draw();
+        assert_eq!(other.remaining(), 3);
+
+        let mut drawn: Vec<u8> = (0..4).filter_map(|_| other.draw_top()).collect();
+        drawn.sort();
+        assert_eq!(drawn, [1, 2, 3]);
+        assert_eq!(deck.draw_top(), None);
+    }
+
+    #[test]
+    fn test_shared_deck_race() {
+        let deck = SharedDeck::new(Deck::from((0..1000).collect::<Vec<u32>>()));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let deck = deck.clone();
+                thread::spawn(move || {
+                    let mut drawn = Vec::new();
+                    while let Some(x) = deck.draw_top() {
+                        drawn.push(x);
+                    }
+                    drawn
+                })
+            })
+            .collect();
+
+        let mut all = Vec::new();
+        for handle in handles {
+            all.extend(handle.join().unwrap());
+        }
+        assert_eq!(all.len(), 1000);
+        assert_eq!(all.iter().collect::<HashSet<_>>().len(), 1000);
+        assert_eq!(deck.remaining(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn test_stream_draw() {
+        let mut stream = SharedDeck::new(Deck::from(vec![1_u8, 2, 3]));
+        stream.close();
+
+        let drawn: Vec<u8> = block_on(stream.by_ref().collect());
+        assert_eq!(drawn, [3, 2, 1]);
+        assert_eq!(block_on(stream.next()), None);
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn test_stream_pending() {
+        let mut stream = SharedDeck::new(Deck::<u8>::new());
+        block_on(async {
+            assert_eq!(poll!(stream.next()), Poll::Pending);
+        });
+
+        stream.put_top(4);
+        assert_eq!(block_on(stream.next()), Some(4));
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn test_stream_wake() {
+        let mut stream = SharedDeck::new(Deck::<u8>::new());
+        let producer = stream.clone();
+        let handle = thread::spawn(move || {
+            for i in 0..5 {
+                thread::sleep(Duration::from_millis(5));
+                producer.put_top(i);
+            }
+            producer.close();
+        });
+
+        let mut drawn: Vec<u8> = block_on(stream.by_ref().collect());
+        handle.join().unwrap();
+        drawn.sort();
+        assert_eq!(drawn, [0, 1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,78 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::Deck;
+
+pub struct DeckTransaction<T> {
+    original: Deck<T>,
+    working: Deck<T>,
+}
+
+impl<T: Clone> DeckTransaction<T> {
+    pub(crate) fn new(original: Deck<T>) -> Self {
+        Self {
+            working: original.clone(),
+            original,
+        }
+    }
+}
+
+impl<T> DeckTransaction<T> {
+    pub fn commit(self) -> Deck<T> {
+        self.working
+    }
+
+    pub fn rollback(self) -> Deck<T> {
+        self.original
+    }
+}
+
+impl<T> Deref for DeckTransaction<T> {
+    type Target = Deck<T>;
+
+    fn deref(&self) -> &Deck<T> {
+        &self.working
+    }
+}
+
+impl<T> DerefMut for DeckTransaction<T> {
+    fn deref_mut(&mut self) -> &mut Deck<T> {
+        &mut self.working
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build() -> Deck<u8> {
+        let mut deck = Deck::from((0..10).collect::<Vec<u8>>());
+        deck.discard(10);
+        deck.remove(11);
+        deck
+    }
+
+    #[test]
+    fn test_commit() {
+        let mut transaction = build().begin_transaction();
+        let hand = transaction.deal_equal(1, 5).unwrap();
+        transaction.discard(hand[0][0]);
+        transaction.shuffle_draw();
+        assert_eq!(transaction.remaining(), 5);
+
+        let deck = transaction.commit();
+        assert_eq!(deck.remaining(), 5);
+        assert_eq!(deck.peek_discard_pile(), [10, 9]);
+        assert_eq!(deck.peek_removed_pile(), [11]);
+    }
+
+    #[test]
+    fn test_rollback() {
+        let mut transaction = build().begin_transaction();
+        assert!(transaction.deal_equal(2, 5).is_some());
+        assert_eq!(transaction.deal_equal(1, 1), None);
+        transaction.mill(10);
+        transaction.remove(12);
+
+        assert_eq!(transaction.rollback(), build());
+    }
+}